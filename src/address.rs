@@ -2,9 +2,10 @@
 
 use super::*;
 use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
 use core::marker::PhantomData;
 use core::mem::{align_of, size_of};
-use core::{ops::*, panic};
+use core::ops::*;
 
 /// An address
 ///
@@ -74,6 +75,12 @@ impl<T: PartialEq, U> PartialEq for Address<T, U> {
 
 impl<T: Eq, U> Eq for Address<T, U> {}
 
+impl<T: Hash, U> Hash for Address<T, U> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
 impl<T: PartialOrd, U> PartialOrd for Address<T, U> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.0.partial_cmp(&other.0)
@@ -102,14 +109,169 @@ impl<U> Address<usize, U> {
     /// Panics if the value is not properly aligned.
     #[inline]
     pub const fn new(value: usize) -> Self {
-        if value % align_of::<U>() != 0 {
-            panic!("unaligned address value");
-        }
-
+        assert!(value % align_of::<U>() == 0, "unaligned address value");
         Self(value, PhantomData)
     }
 }
 
+macro_rules! implconst {
+    ($($t:ident)+) => {
+        $(
+            impl<U> Address<$t, U> {
+                /// Converts an `Address` to its raw inner type in a `const` context
+                #[inline]
+                pub const fn const_raw(self) -> $t {
+                    self.0
+                }
+
+                /// Aligns up to a different type in a `const` context
+                ///
+                /// This is the `const` equivalent of `raise()`.
+                #[inline]
+                pub const fn const_raise<V>(self) -> Address<$t, V> {
+                    let align = align_of::<V>() as $t;
+                    Address(self.0.next_multiple_of(align), PhantomData)
+                }
+
+                /// Aligns down to a different type in a `const` context
+                ///
+                /// This is the `const` equivalent of `lower()`.
+                #[inline]
+                pub const fn const_lower<V>(self) -> Address<$t, V> {
+                    let align = align_of::<V>() as $t;
+                    Address(self.0 / align * align, PhantomData)
+                }
+
+                /// Tries casting to a different type in a `const` context
+                ///
+                /// This is the `const` equivalent of `try_cast()`.
+                #[inline]
+                pub const fn const_try_cast<V>(self) -> Result<Address<$t, V>, Error> {
+                    if self.0 % align_of::<V>() as $t != 0 {
+                        return Err(Error::Misaligned {
+                            address: self.0 as u128,
+                            align: align_of::<V>(),
+                        });
+                    }
+
+                    Ok(Address(self.0, PhantomData))
+                }
+
+                /// Adds an `Offset` in a `const` context
+                ///
+                /// Overflow is a compile-time error when evaluated in a
+                /// `const` or `static` item.
+                #[inline]
+                pub const fn const_add(self, rhs: Offset<$t, U>) -> Self {
+                    Self(self.0 + rhs.const_bytes(), PhantomData)
+                }
+
+                /// Subtracts an `Offset` in a `const` context
+                ///
+                /// Underflow is a compile-time error when evaluated in a
+                /// `const` or `static` item.
+                #[inline]
+                pub const fn const_sub(self, rhs: Offset<$t, U>) -> Self {
+                    Self(self.0 - rhs.const_bytes(), PhantomData)
+                }
+            }
+        )+
+    };
+}
+
+implconst! { usize u64 }
+
+macro_rules! impldistance {
+    ($($t:ident:$s:ident)+) => {
+        $(
+            impl<U> Address<$t, U> {
+                /// Returns the distance in items, its remainder in bytes and
+                /// whether it is negative
+                ///
+                /// Returns `None` for the item count if `U` is zero-sized.
+                #[inline]
+                fn distance(self, origin: Self) -> (Option<$t>, $t, bool) {
+                    let (bytes, negative) = match self.0 >= origin.0 {
+                        true => (self.0 - origin.0, false),
+                        false => (origin.0 - self.0, true),
+                    };
+
+                    match <$t>::try_from(size_of::<U>()) {
+                        Ok(0) => (None, 0, negative),
+                        Ok(size) => (Some(bytes / size), bytes % size, negative),
+                        Err(..) => (Some(0), bytes, negative),
+                    }
+                }
+
+                /// Returns the signed distance from `origin` to `self` in items
+                ///
+                /// The distance is negative if `self` is below `origin`. It is
+                /// truncated towards zero if it is not a whole number of items,
+                /// and it is zero if `U` is zero-sized.
+                ///
+                /// A distance that does not fit in the signed offset type wraps
+                /// around silently, in either direction. For example, the
+                /// highest `u64` byte address is `-1` bytes from the lowest one.
+                /// Use `checked_offset_from()` or `exact_offset_from()` to
+                /// detect this.
+                #[inline]
+                pub fn offset_from(self, origin: Self) -> Offset<$s, U> {
+                    let items = match self.distance(origin) {
+                        (Some(items), _, false) => items as $s,
+                        (Some(items), _, true) => (0 as $s).wrapping_sub_unsigned(items),
+                        (None, ..) => 0,
+                    };
+
+                    Offset::from_items(items)
+                }
+
+                /// Returns the signed distance from `origin` to `self` in items
+                ///
+                /// Returns `None` if the distance is not a whole number of
+                /// items, if it does not fit in the offset type or if `U` is
+                /// zero-sized.
+                #[inline]
+                pub fn checked_offset_from(self, origin: Self) -> Option<Offset<$s, U>> {
+                    let items = match self.distance(origin) {
+                        (None, ..) | (Some(_), 1.., _) => return None,
+                        (Some(items), 0, false) => <$s>::try_from(items).ok()?,
+                        (Some(items), 0, true) => (0 as $s).checked_sub_unsigned(items)?,
+                    };
+
+                    Some(Offset::from_items(items))
+                }
+
+                /// Returns the exact signed distance from `origin` to `self` in items
+                ///
+                /// This matches the guarantees of `pointer::offset_from`, but
+                /// reports violations instead of causing undefined behavior:
+                /// `Error::Inexact` if the distance is not a whole number of
+                /// items, `Error::Overflow` if it does not fit in the offset
+                /// type and `Error::InvalidSize` if `U` is zero-sized.
+                #[inline]
+                pub fn exact_offset_from(
+                    self,
+                    origin: Self,
+                ) -> Result<Offset<$s, U>, Error> {
+                    let items = match self.distance(origin) {
+                        (None, ..) => return Err(Error::InvalidSize),
+                        (Some(_), 1.., _) => return Err(Error::Inexact),
+                        (Some(items), 0, false) => <$s>::try_from(items).ok(),
+                        (Some(items), 0, true) => (0 as $s).checked_sub_unsigned(items),
+                    };
+
+                    match items {
+                        Some(items) => Ok(Offset::from_items(items)),
+                        None => Err(Error::Overflow),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impldistance! { u8:i8 u16:i16 u32:i32 u64:i64 u128:i128 usize:isize }
+
 impl<T, U> Address<T, U> {
     /// Creates a new `Address` from a raw inner type without checking
     ///
@@ -138,26 +300,50 @@ where
 {
     /// Returns a raw pointer to its inner type
     ///
+    /// The pointer picks up whatever provenance was previously exposed for
+    /// this address. Prefer `with_provenance_of()` where a suitable pointer
+    /// is available.
+    ///
     /// # Safety
     /// Behavior is undefined, if the pointer is used and
     /// is not aligned or points to uninitialized memory.
     #[inline]
     pub fn as_ptr(self) -> *const U {
-        self.into().0 as *const U
+        core::ptr::with_exposed_provenance(self.into().0)
     }
 
     /// Returns a raw pointer to its inner type
     ///
+    /// The pointer picks up whatever provenance was previously exposed for
+    /// this address. Prefer `with_provenance_of_mut()` where a suitable
+    /// pointer is available.
+    ///
     /// # Safety
     /// Behavior is undefined, if the pointer is used and
     /// is not aligned or points to uninitialized memory.
     #[inline]
     pub fn as_mut_ptr(self) -> *mut U {
-        self.into().0 as *mut U
+        core::ptr::with_exposed_provenance_mut(self.into().0)
     }
-}
 
-pub struct AlignmentError;
+    /// Returns a pointer to this address with the provenance of `ptr`
+    ///
+    /// This is the strict provenance equivalent of `as_ptr()`: the result may
+    /// only be used to access memory that `ptr` itself could access.
+    #[inline]
+    pub fn with_provenance_of<V>(self, ptr: *const V) -> *const U {
+        ptr.cast::<U>().with_addr(self.into().0)
+    }
+
+    /// Returns a mutable pointer to this address with the provenance of `ptr`
+    ///
+    /// This is the strict provenance equivalent of `as_mut_ptr()`: the result
+    /// may only be used to access memory that `ptr` itself could access.
+    #[inline]
+    pub fn with_provenance_of_mut<V>(self, ptr: *mut V) -> *mut U {
+        ptr.cast::<U>().with_addr(self.into().0)
+    }
+}
 
 impl<T, U> Address<T, U>
 where
@@ -168,11 +354,14 @@ where
     ///
     /// Succeeds only, if they have compatible alignment
     #[inline]
-    pub fn try_cast<V>(self) -> Result<Address<T, V>, AlignmentError> {
+    pub fn try_cast<V>(self) -> Result<Address<T, V>, Error> {
         let addr = self.into();
 
         if addr.0 % align_of::<V>() != 0 {
-            return Err(AlignmentError);
+            return Err(Error::Misaligned {
+                address: addr.0 as u128,
+                align: align_of::<V>(),
+            });
         }
 
         Ok(Address(Self::from(addr).0, PhantomData))
@@ -203,6 +392,116 @@ where
     }
 }
 
+impl<T, U> Address<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Checked,
+{
+    /// Checked addition of an `Offset`
+    ///
+    /// Returns `None` if the resulting address overflows.
+    #[inline]
+    pub fn checked_add(self, rhs: Offset<T, U>) -> Option<Self> {
+        let bytes = rhs.checked_bytes()?;
+        Some(Self(self.0.checked_add(bytes)?, PhantomData))
+    }
+
+    /// Checked subtraction of an `Offset`
+    ///
+    /// Returns `None` if the resulting address underflows.
+    #[inline]
+    pub fn checked_sub(self, rhs: Offset<T, U>) -> Option<Self> {
+        let bytes = rhs.checked_bytes()?;
+        Some(Self(self.0.checked_sub(bytes)?, PhantomData))
+    }
+}
+
+impl<T, U> Address<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Overflowing,
+{
+    /// Overflowing addition of an `Offset`
+    ///
+    /// Returns the wrapped address and whether an overflow occurred.
+    /// Wrapping preserves the alignment of the address.
+    #[inline]
+    pub fn overflowing_add(self, rhs: Offset<T, U>) -> (Self, bool) {
+        let size: T = Offset::from_items(size_of::<U>()).into().items();
+        let (bytes, a) = rhs.items().overflowing_mul(size);
+        let (value, b) = self.0.overflowing_add(bytes);
+        (Self(value, PhantomData), a || b)
+    }
+
+    /// Overflowing subtraction of an `Offset`
+    ///
+    /// Returns the wrapped address and whether an overflow occurred.
+    /// Wrapping preserves the alignment of the address.
+    #[inline]
+    pub fn overflowing_sub(self, rhs: Offset<T, U>) -> (Self, bool) {
+        let size: T = Offset::from_items(size_of::<U>()).into().items();
+        let (bytes, a) = rhs.items().overflowing_mul(size);
+        let (value, b) = self.0.overflowing_sub(bytes);
+        (Self(value, PhantomData), a || b)
+    }
+}
+
+impl<T, U> Address<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Wrapping,
+{
+    /// Wrapping addition of an `Offset`
+    ///
+    /// Wrapping preserves the alignment of the address.
+    #[inline]
+    pub fn wrapping_add(self, rhs: Offset<T, U>) -> Self {
+        let size: T = Offset::from_items(size_of::<U>()).into().items();
+        Self(
+            self.0.wrapping_add(rhs.items().wrapping_mul(size)),
+            PhantomData,
+        )
+    }
+
+    /// Wrapping subtraction of an `Offset`
+    ///
+    /// Wrapping preserves the alignment of the address.
+    #[inline]
+    pub fn wrapping_sub(self, rhs: Offset<T, U>) -> Self {
+        let size: T = Offset::from_items(size_of::<U>()).into().items();
+        Self(
+            self.0.wrapping_sub(rhs.items().wrapping_mul(size)),
+            PhantomData,
+        )
+    }
+}
+
+impl<T, U> Address<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Checked + Wrapping + Zero,
+{
+    /// Saturating addition of an `Offset`
+    ///
+    /// On overflow, this returns the highest address that is still properly
+    /// aligned for `U` rather than the maximum value of `T`.
+    #[inline]
+    pub fn saturating_add(self, rhs: Offset<T, U>) -> Self {
+        self.checked_add(rhs).unwrap_or_else(|| {
+            let align: T = Offset::from_items(align_of::<U>()).into().items();
+            Self(T::ZERO.wrapping_sub(align), PhantomData)
+        })
+    }
+
+    /// Saturating subtraction of an `Offset`
+    ///
+    /// On underflow, this returns the `NULL` address.
+    #[inline]
+    pub fn saturating_sub(self, rhs: Offset<T, U>) -> Self {
+        self.checked_sub(rhs).unwrap_or(Self::NULL)
+    }
+}
+
 /// Convert a raw address value to an untyped `Address`
 impl<T> From<T> for Address<T, ()> {
     #[inline]
@@ -218,7 +517,7 @@ where
 {
     #[inline]
     fn from(value: &U) -> Self {
-        Address(value as *const U as usize, PhantomData).into()
+        Address((value as *const U).expose_provenance(), PhantomData).into()
     }
 }
 
@@ -229,7 +528,7 @@ where
 {
     #[inline]
     fn from(value: *mut U) -> Self {
-        Address(value as usize, PhantomData).into()
+        Address(value.expose_provenance(), PhantomData).into()
     }
 }
 
@@ -240,7 +539,7 @@ where
 {
     #[inline]
     fn from(value: *const U) -> Self {
-        Address(value as usize, PhantomData).into()
+        Address(value.expose_provenance(), PhantomData).into()
     }
 }
 
@@ -295,6 +594,8 @@ impl<U> From<Address<usize, U>> for Address<u32, U> {
     }
 }
 
+implwidths!(Address);
+
 impl<T, U> Add<Offset<T, U>> for Address<T, U>
 where
     Offset<usize, ()>: Into<Offset<T, ()>>,
@@ -379,9 +680,130 @@ mod test {
         assert_eq!(Address::from(7usize).lower::<u32>().raw(), 4);
     }
 
+    #[test]
+    fn checked() {
+        let addr = Address::from(u64::MAX - 7).lower::<u32>();
+        let one = Offset::from_items(1);
+        let two = Offset::from_items(2);
+
+        assert_eq!(addr.checked_add(one).unwrap().raw(), u64::MAX - 3);
+        assert_eq!(addr.checked_add(two), None);
+        assert_eq!(addr.checked_add(Offset::from_items(u64::MAX)), None);
+        assert_eq!(Address::<u64, u32>::NULL.checked_sub(one), None);
+    }
+
+    #[test]
+    fn wrapping() {
+        let addr = Address::from(u64::MAX - 3).lower::<u32>();
+        let one = Offset::from_items(1);
+
+        assert_eq!(addr.overflowing_add(one), (Address::NULL, true));
+        assert_eq!(addr.wrapping_add(one), Address::NULL);
+        assert_eq!(Address::<u64, u32>::NULL.wrapping_sub(one), addr);
+        assert!(!addr.overflowing_sub(one).1);
+    }
+
+    #[test]
+    fn saturating() {
+        let addr = Address::<usize, Page>::NULL;
+        let top = addr.saturating_add(Offset::from_items(usize::MAX));
+
+        assert_eq!(top.raw(), usize::MAX - Page::SIZE + 1);
+        assert_eq!(top.saturating_add(Offset::from_items(1)), top);
+        assert_eq!(addr.saturating_sub(Offset::from_items(1)), addr);
+    }
+
+    #[test]
+    fn provenance() {
+        let mut buf = [0u32; 4];
+        let base = buf.as_mut_ptr();
+
+        let addr = Address::<usize, u32>::new(base.addr()) + Offset::from_items(2);
+        unsafe { addr.with_provenance_of_mut(base).write(7) };
+        assert_eq!(buf[2], 7);
+
+        let ptr = addr.with_provenance_of(buf.as_ptr());
+        assert_eq!(unsafe { ptr.read() }, 7);
+    }
+
+    #[test]
+    fn constant() {
+        const HEAP: Address<usize, Page> = Address::<usize, u8>::new(0x1234).const_raise();
+        const STACK: Address<u64, u64> = unsafe { Address::<u64, Page>::unchecked(0x8000) }
+            .const_lower()
+            .const_sub(Offset::from_items(1));
+        const TOP: usize = HEAP.const_add(Offset::from_items(2)).const_raw();
+
+        assert_eq!(HEAP.raw(), 0x2000);
+        assert_eq!(STACK.lower::<Page>().raw(), 0x7000);
+        assert_eq!(STACK.const_lower::<Page>().raw(), 0x7000);
+        assert_eq!(TOP, 0x4000);
+        assert_eq!(
+            STACK.const_try_cast::<Page>(),
+            Err(Error::Misaligned {
+                address: 0x7ff8,
+                align: Page::SIZE
+            })
+        );
+        assert!(HEAP.const_try_cast::<u32>().is_ok());
+    }
+
     #[test]
     fn print_pointer() {
         println!("{:p}", Address::from(4usize).raise::<Page>());
         println!("{:p}", Address::from(7u64).lower::<u32>());
     }
+
+    #[test]
+    fn try_from() {
+        let addr = Address::from(0x1_0000_1000u64).lower::<u32>();
+        assert_eq!(Address::<u32, u32>::try_from(addr), Err(Error::OutOfRange));
+        assert_eq!(Address::<u128, u32>::from(addr).raw(), 0x1_0000_1000);
+
+        let addr = Address::from(0x1000u64).lower::<u32>();
+        let addr = Address::<u16, u32>::try_from(addr).unwrap();
+        assert_eq!(Address::<usize, u32>::from(addr).raw(), 0x1000);
+
+        let addr = Address::from(u128::MAX);
+        assert_eq!(Address::<usize, ()>::try_from(addr), Err(Error::OutOfRange));
+    }
+
+    #[test]
+    fn offset_from() {
+        let a = Address::<usize, u32>::new(0x1000);
+        let b = Address::<usize, u32>::new(0x1010);
+        assert_eq!(b.offset_from(a), Offset::from_items(4));
+        assert_eq!(a.offset_from(b), Offset::from_items(-4));
+        assert_eq!(a.exact_offset_from(b), Ok(Offset::from_items(-4)));
+
+        let a = Address::<usize, [u8; 3]>::new(0);
+        let b = Address::<usize, [u8; 3]>::new(7);
+        assert_eq!(b.offset_from(a), Offset::from_items(2));
+        assert_eq!(a.offset_from(b), Offset::from_items(-2));
+        assert_eq!(b.checked_offset_from(a), None);
+        assert_eq!(a.checked_offset_from(b), None);
+        assert_eq!(b.exact_offset_from(a), Err(Error::Inexact));
+
+        let c = Address::<usize, [u8; 3]>::new(6);
+        assert_eq!(a.checked_offset_from(c), Some(Offset::from_items(-2)));
+
+        let low = Address::from(0u64).lower::<u8>();
+        let high = Address::from(u64::MAX).lower::<u8>();
+        assert_eq!(high.offset_from(low), Offset::from_items(-1));
+        assert_eq!(high.checked_offset_from(low), None);
+        assert_eq!(low.exact_offset_from(high), Err(Error::Overflow));
+
+        let min = Address::from(1u64 << 63).lower::<u8>();
+        assert_eq!(
+            low.checked_offset_from(min),
+            Some(Offset::from_items(i64::MIN))
+        );
+
+        let unit = Address::<usize, ()>::new(8);
+        assert_eq!(unit.offset_from(Address::NULL), Offset::from_items(0));
+        assert_eq!(
+            unit.exact_offset_from(Address::NULL),
+            Err(Error::InvalidSize)
+        );
+    }
 }
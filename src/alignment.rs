@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::mem::align_of;
+use core::ops::*;
+
+/// A runtime alignment
+///
+/// This type holds an alignment that is only known at runtime, such as a
+/// page size from the auxiliary vector or an ELF segment's `p_align`. It is
+/// always a non-zero power of two.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Alignment(usize);
+
+impl Alignment {
+    /// The alignment of a `Page`
+    pub const PAGE: Self = Self::of::<Page>();
+
+    /// Creates a new alignment
+    ///
+    /// Fails if the value is not a power of two.
+    #[inline]
+    pub const fn new(value: usize) -> Result<Self, Error> {
+        match value.is_power_of_two() {
+            true => Ok(Self(value)),
+            false => Err(Error::InvalidSize),
+        }
+    }
+
+    /// Returns the alignment of the type `V`
+    #[inline]
+    pub const fn of<V>() -> Self {
+        Self(align_of::<V>())
+    }
+
+    /// Returns the alignment in bytes
+    #[inline]
+    pub const fn get(self) -> usize {
+        self.0
+    }
+
+    /// Returns the base-2 logarithm of the alignment
+    #[inline]
+    pub const fn log2(self) -> u32 {
+        self.0.trailing_zeros()
+    }
+}
+
+impl TryFrom<usize> for Alignment {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Alignment> for usize {
+    #[inline]
+    fn from(value: Alignment) -> Self {
+        value.0
+    }
+}
+
+impl<T, U> Address<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + Eq + Zero + Checked,
+    T: Sub<T, Output = T>,
+    T: Rem<T, Output = T>,
+{
+    /// Returns whether the address is a multiple of `align`
+    #[inline]
+    pub fn is_aligned_to(self, align: Alignment) -> bool {
+        let align: T = Offset::from_items(align.0).into().items();
+        self.raw() % align == T::ZERO
+    }
+
+    /// Returns the number of bytes needed to align the address up to `align`
+    #[inline]
+    pub fn align_offset(self, align: Alignment) -> Offset<T, u8> {
+        let align: T = Offset::from_items(align.0).into().items();
+        match self.raw() % align {
+            rem if rem == T::ZERO => Offset::from_items(T::ZERO),
+            rem => Offset::from_items(align - rem),
+        }
+    }
+
+    /// Aligns the address up to `align`
+    ///
+    /// The result remains properly aligned for `U`. Fails if the aligned
+    /// address does not fit in `T`.
+    #[inline]
+    pub fn align_up(self, align: Alignment) -> Result<Self, Error> {
+        let value = self.raw();
+        let bytes = Address::from(value).align_offset(align).items();
+        match value.checked_add(bytes) {
+            Some(value) => Ok(unsafe { Self::unchecked(value) }),
+            None => Err(Error::Overflow),
+        }
+    }
+
+    /// Aligns the address down to `align`
+    ///
+    /// The result remains properly aligned for `U`.
+    #[inline]
+    pub fn align_down(self, align: Alignment) -> Self {
+        let align: T = Offset::from_items(align.0).into().items();
+        let value = self.raw();
+        unsafe { Self::unchecked(value - value % align) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn alignment() {
+        assert_eq!(Alignment::new(0), Err(Error::InvalidSize));
+        assert_eq!(Alignment::new(3), Err(Error::InvalidSize));
+        assert_eq!(Alignment::new(0x20_0000).unwrap().log2(), 21);
+        assert_eq!(Alignment::PAGE.get(), Page::SIZE);
+        assert_eq!(Alignment::try_from(8), Ok(Alignment::of::<u64>()));
+    }
+
+    #[test]
+    fn align() {
+        let huge = Alignment::new(0x20_0000).unwrap();
+        let addr = Address::from(0x1234_5000u64).lower::<Page>();
+
+        assert_eq!(addr.align_up(huge).unwrap().raw(), 0x1240_0000);
+        assert_eq!(addr.align_down(huge).raw(), 0x1220_0000);
+        assert_eq!(addr.align_offset(huge), Offset::from_items(0xb_b000));
+        assert!(!addr.is_aligned_to(huge));
+        assert!(addr.is_aligned_to(Alignment::PAGE));
+        assert_eq!(addr.align_offset(Alignment::PAGE), Offset::from_items(0));
+
+        let top = Address::<usize, ()>::from(usize::MAX - 0x1000);
+        assert_eq!(top.align_up(huge), Err(Error::Overflow));
+    }
+}
@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::cmp::Ordering;
+use core::mem::align_of;
+
+/// An x86-64 paging mode
+///
+/// The paging mode determines how many bits of a linear address are
+/// significant. All bits above the most significant implemented bit must be
+/// copies of it for the address to be canonical.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PagingMode {
+    /// 4-level paging with 48-bit linear addresses
+    Level4,
+
+    /// 5-level paging (LA57) with 57-bit linear addresses
+    Level5,
+}
+
+impl PagingMode {
+    /// Returns the number of significant bits in a linear address
+    #[inline]
+    pub const fn bits(self) -> u32 {
+        match self {
+            Self::Level4 => 48,
+            Self::Level5 => 57,
+        }
+    }
+
+    /// Sign-extends the value from the most significant implemented bit
+    #[inline]
+    const fn extend(self, value: u64) -> u64 {
+        let shift = 64 - self.bits();
+        ((value << shift) as i64 >> shift) as u64
+    }
+}
+
+impl<U> Address<u64, U> {
+    /// Returns whether the address is canonical in the given paging mode
+    #[inline]
+    pub fn is_canonical(self, mode: PagingMode) -> bool {
+        let value = self.raw();
+        mode.extend(value) == value
+    }
+
+    /// Makes the address canonical by sign-extending it
+    ///
+    /// The bits above the most significant implemented bit are replaced by
+    /// copies of it. This never changes the alignment of the address.
+    #[inline]
+    pub fn canonicalize(self, mode: PagingMode) -> Self {
+        unsafe { Self::unchecked(mode.extend(self.raw())) }
+    }
+
+    /// Creates a new address, checking that it is aligned and canonical
+    ///
+    /// Fails with `Error::Misaligned` if the value is not properly aligned
+    /// for `U` and with `Error::NonCanonical` if it is not canonical in the
+    /// given paging mode.
+    #[inline]
+    pub fn try_canonical(value: u64, mode: PagingMode) -> Result<Self, Error> {
+        if value % align_of::<U>() as u64 != 0 {
+            return Err(Error::Misaligned {
+                address: value.into(),
+                align: align_of::<U>(),
+            });
+        }
+
+        if mode.extend(value) != value {
+            return Err(Error::NonCanonical);
+        }
+
+        Ok(unsafe { Self::unchecked(value) })
+    }
+}
+
+/// A canonical x86-64 address
+///
+/// This type wraps an `Address<u64, U>` that is guaranteed to be canonical
+/// in the paging mode it was validated against.
+#[derive(Copy, Clone)]
+pub struct CanonicalAddress<U> {
+    addr: Address<u64, U>,
+    mode: PagingMode,
+}
+
+impl<U> core::fmt::Debug for CanonicalAddress<U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("CanonicalAddress")
+            .field(&self.addr)
+            .field(&self.mode)
+            .finish()
+    }
+}
+
+impl<U> PartialEq for CanonicalAddress<U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.addr == other.addr
+    }
+}
+
+impl<U> Eq for CanonicalAddress<U> {}
+
+impl<U> PartialOrd for CanonicalAddress<U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<U> Ord for CanonicalAddress<U> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.addr.cmp(&other.addr)
+    }
+}
+
+impl<U> CanonicalAddress<U> {
+    /// Validates that an address is canonical in the given paging mode
+    ///
+    /// Fails with `Error::NonCanonical` if it is not.
+    #[inline]
+    pub fn new(addr: Address<u64, U>, mode: PagingMode) -> Result<Self, Error> {
+        Self::try_new(addr.raw(), mode)
+    }
+
+    /// Creates a canonical address from a raw value
+    ///
+    /// Fails with `Error::Misaligned` if the value is not properly aligned
+    /// for `U` and with `Error::NonCanonical` if it is not canonical in the
+    /// given paging mode.
+    #[inline]
+    pub fn try_new(value: u64, mode: PagingMode) -> Result<Self, Error> {
+        let addr = Address::try_canonical(value, mode)?;
+        Ok(Self { addr, mode })
+    }
+
+    /// Creates a canonical address from a register value
+    ///
+    /// Fails in the same way as `try_new()`.
+    #[inline]
+    pub fn from_register(value: Register<u64>, mode: PagingMode) -> Result<Self, Error> {
+        Self::try_new(value.into(), mode)
+    }
+
+    /// Returns the paging mode the address was validated against
+    #[inline]
+    pub fn mode(&self) -> PagingMode {
+        self.mode
+    }
+
+    /// Returns the underlying address
+    #[inline]
+    pub fn address(self) -> Address<u64, U> {
+        self.addr
+    }
+
+    /// Converts a `CanonicalAddress` to its raw value
+    #[inline]
+    pub fn raw(self) -> u64 {
+        self.addr.raw()
+    }
+}
+
+impl<U> From<CanonicalAddress<U>> for Address<u64, U> {
+    #[inline]
+    fn from(value: CanonicalAddress<U>) -> Self {
+        value.addr
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn canonical() {
+        let low = Address::<u64, ()>::from(0x0000_7fff_ffff_f000);
+        let high = Address::<u64, ()>::from(0xffff_8000_0000_0000);
+        let hole = Address::<u64, ()>::from(0x0000_8000_0000_0000);
+
+        assert!(low.is_canonical(PagingMode::Level4));
+        assert!(high.is_canonical(PagingMode::Level4));
+        assert!(!hole.is_canonical(PagingMode::Level4));
+        assert!(hole.is_canonical(PagingMode::Level5));
+        assert!(high.is_canonical(PagingMode::Level5));
+
+        let la57 = Address::<u64, ()>::from(0x0100_0000_0000_0000);
+        assert!(!la57.is_canonical(PagingMode::Level5));
+
+        assert_eq!(
+            hole.canonicalize(PagingMode::Level4).raw(),
+            0xffff_8000_0000_0000
+        );
+        assert_eq!(
+            la57.canonicalize(PagingMode::Level5).raw(),
+            0xff00_0000_0000_0000
+        );
+    }
+
+    #[test]
+    fn try_canonical() {
+        let mode = PagingMode::Level4;
+
+        assert!(Address::<u64, Page>::try_canonical(0xffff_8000_0000_0000, mode).is_ok());
+        assert_eq!(
+            Address::<u64, Page>::try_canonical(0xffff_8000_0000_0008, mode),
+            Err(Error::Misaligned {
+                address: 0xffff_8000_0000_0008,
+                align: Page::SIZE
+            })
+        );
+        assert_eq!(
+            Address::<u64, Page>::try_canonical(0x0000_8000_0000_0000, mode),
+            Err(Error::NonCanonical)
+        );
+
+        let reg = Register::<u64>::from(0xffff_ffff_ffff_fff8u64);
+        let addr = CanonicalAddress::<u64>::from_register(reg, mode).unwrap();
+        assert_eq!(addr.raw(), 0xffff_ffff_ffff_fff8);
+        assert_eq!(addr.mode(), mode);
+
+        let reg = Register::<u64>::from(0x1234_5678_9abc_def0u64);
+        assert_eq!(
+            CanonicalAddress::<u64>::from_register(reg, mode),
+            Err(Error::NonCanonical)
+        );
+    }
+}
@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// A physical address bit which selects memory encryption
+///
+/// Confidential VMs mark guest physical addresses, and the page-table
+/// entries which map them, as private or shared using a single high bit.
+/// On AMD SEV this is the C-bit, whose position is reported by CPUID, and a
+/// set bit means private. On Intel TDX this is the shared GPA bit, and a set
+/// bit means shared.
+///
+/// The bit must lie between bit 12 and bit 62, so that applying it never
+/// changes the page offset of an address nor the NX bit of an entry.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EncryptionMask {
+    bit: u32,
+    private: bool,
+}
+
+impl EncryptionMask {
+    /// Creates a mask for the SEV C-bit at the given position
+    ///
+    /// Fails with `Error::OutOfRange` if the bit is outside of the supported
+    /// range.
+    #[inline]
+    pub const fn sev(bit: u32) -> Result<Self, Error> {
+        Self::new(bit, true)
+    }
+
+    /// Creates a mask for the TDX shared bit at the given position
+    ///
+    /// Fails with `Error::OutOfRange` if the bit is outside of the supported
+    /// range.
+    #[inline]
+    pub const fn tdx(bit: u32) -> Result<Self, Error> {
+        Self::new(bit, false)
+    }
+
+    #[inline]
+    const fn new(bit: u32, private: bool) -> Result<Self, Error> {
+        match bit {
+            12..=62 => Ok(Self { bit, private }),
+            _ => Err(Error::OutOfRange),
+        }
+    }
+
+    /// Returns the position of the bit
+    #[inline]
+    pub const fn bit(self) -> u32 {
+        self.bit
+    }
+
+    /// Returns the mask of the bit
+    #[inline]
+    pub const fn mask(self) -> u64 {
+        1 << self.bit
+    }
+
+    /// Returns whether the bit is set in the address
+    #[inline]
+    pub fn test<U>(self, addr: Address<u64, U>) -> bool {
+        addr.raw() & self.mask() != 0
+    }
+
+    /// Sets the bit in the address
+    ///
+    /// Fails with `Error::OutOfRange` if the bit is already set, since the
+    /// address then collides with the mask.
+    #[inline]
+    pub fn set<U>(self, addr: Address<u64, U>) -> Result<Address<u64, U>, Error> {
+        let addr = self.check(addr)?;
+        Ok(unsafe { Address::unchecked(addr.raw() | self.mask()) })
+    }
+
+    /// Clears the bit in the address
+    ///
+    /// This strips the encryption state, which should be done before
+    /// comparing addresses or checking them against a memory map.
+    #[inline]
+    pub fn clear<U>(self, addr: Address<u64, U>) -> Address<u64, U> {
+        unsafe { Address::unchecked(addr.raw() & !self.mask()) }
+    }
+
+    /// Checks that the address does not collide with the bit
+    ///
+    /// Fails with `Error::OutOfRange` if the bit is set in the address.
+    #[inline]
+    pub fn check<U>(self, addr: Address<u64, U>) -> Result<Address<u64, U>, Error> {
+        let value = addr.raw();
+        match value & self.mask() {
+            0 => Ok(unsafe { Address::unchecked(value) }),
+            _ => Err(Error::OutOfRange),
+        }
+    }
+
+    /// Returns whether the address refers to private memory
+    #[inline]
+    pub fn is_private<U>(self, addr: Address<u64, U>) -> bool {
+        self.is_private_entry(addr.raw())
+    }
+
+    /// Marks the address as private
+    ///
+    /// Fails with `Error::OutOfRange` if the address collides with the bit.
+    #[inline]
+    pub fn private<U>(self, addr: Address<u64, U>) -> Result<Address<u64, U>, Error> {
+        match self.private {
+            true => self.set(addr),
+            false => self.check(addr),
+        }
+    }
+
+    /// Marks the address as shared
+    ///
+    /// Fails with `Error::OutOfRange` if the address collides with the bit.
+    #[inline]
+    pub fn shared<U>(self, addr: Address<u64, U>) -> Result<Address<u64, U>, Error> {
+        match self.private {
+            true => self.check(addr),
+            false => self.set(addr),
+        }
+    }
+
+    /// Returns whether the page-table entry maps private memory
+    #[inline]
+    pub const fn is_private_entry(self, entry: u64) -> bool {
+        (entry & self.mask() != 0) == self.private
+    }
+
+    /// Marks the page-table entry as mapping private memory
+    #[inline]
+    pub const fn private_entry(self, entry: u64) -> u64 {
+        match self.private {
+            true => entry | self.mask(),
+            false => entry & !self.mask(),
+        }
+    }
+
+    /// Marks the page-table entry as mapping shared memory
+    #[inline]
+    pub const fn shared_entry(self, entry: u64) -> u64 {
+        match self.private {
+            true => entry & !self.mask(),
+            false => entry | self.mask(),
+        }
+    }
+
+    /// Clears the bit in the page-table entry
+    #[inline]
+    pub const fn clear_entry(self, entry: u64) -> u64 {
+        entry & !self.mask()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn range() {
+        assert_eq!(EncryptionMask::sev(11), Err(Error::OutOfRange));
+        assert_eq!(EncryptionMask::tdx(63), Err(Error::OutOfRange));
+        assert_eq!(EncryptionMask::tdx(51).unwrap().mask(), 1 << 51);
+    }
+
+    #[test]
+    fn sev() {
+        let mask = EncryptionMask::sev(51).unwrap();
+        let addr = Address::from(0x1000u64).lower::<Page>();
+
+        let private = mask.private(addr).unwrap();
+        assert_eq!(private.raw(), 0x0008_0000_0000_1000);
+        assert!(mask.is_private(private));
+        assert!(!mask.is_private(addr));
+        assert_eq!(mask.shared(addr), Ok(addr));
+        assert_eq!(mask.clear(private), addr);
+        assert_eq!(mask.set(private), Err(Error::OutOfRange));
+        assert_eq!(mask.shared(private), Err(Error::OutOfRange));
+
+        assert_eq!(mask.private_entry(0x1003), 0x0008_0000_0000_1003);
+        assert!(mask.is_private_entry(0x0008_0000_0000_1003));
+    }
+
+    #[test]
+    fn tdx() {
+        let mask = EncryptionMask::tdx(47).unwrap();
+        let addr = Address::from(0x2000u64).lower::<Page>();
+
+        let shared = mask.shared(addr).unwrap();
+        assert_eq!(shared.raw(), 0x0000_8000_0000_2000);
+        assert!(!mask.is_private(shared));
+        assert!(mask.is_private(addr));
+        assert_eq!(mask.private(addr), Ok(addr));
+        assert_eq!(mask.private(shared), Err(Error::OutOfRange));
+
+        let entry = 0x8000_0000_0000_2007;
+        assert_eq!(mask.shared_entry(entry), 0x8000_8000_0000_2007);
+        assert_eq!(mask.clear_entry(mask.shared_entry(entry)), entry);
+        assert_eq!(mask.private_entry(entry), entry);
+    }
+}
@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: Apache-2.0
+
+/// The error type for all fallible operations in this crate
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// An address is not aligned to the required alignment
+    Misaligned {
+        /// The misaligned address
+        address: u128,
+
+        /// The required alignment in bytes
+        align: usize,
+    },
+
+    /// An arithmetic operation overflowed
+    Overflow,
+
+    /// A value is outside of the range of the target type or region
+    OutOfRange,
+
+    /// A size or alignment is zero, not a power of two or otherwise invalid
+    InvalidSize,
+
+    /// A byte count or distance is not a whole number of items
+    Inexact,
+
+    /// An address is null
+    Null,
+
+    /// An address is not canonical in the paging mode
+    NonCanonical,
+
+    /// A fixed-capacity collection is full
+    CapacityExceeded,
+
+    /// A string to parse is empty
+    Empty,
+
+    /// A string to parse contains an invalid digit
+    InvalidDigit,
+
+    /// A string to parse has an unknown size suffix
+    InvalidSuffix,
+
+    /// An address is not mapped
+    Unmapped,
+
+    /// A page table is malformed or outside of the available memory
+    InvalidPageTable,
+
+    /// Two entries of a list refer to overlapping memory
+    Overlap,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Misaligned { address, align } => f.write_fmt(format_args!(
+                "address 0x{:x} is not aligned to {} bytes",
+                address, align
+            )),
+            Self::Overflow => f.write_str("arithmetic overflow"),
+            Self::OutOfRange => f.write_str("value out of range"),
+            Self::InvalidSize => f.write_str("invalid size or alignment"),
+            Self::Inexact => f.write_str("not a whole number of items"),
+            Self::Null => f.write_str("address is null"),
+            Self::NonCanonical => f.write_str("address is not canonical"),
+            Self::CapacityExceeded => f.write_str("capacity exceeded"),
+            Self::Empty => f.write_str("cannot parse an empty string"),
+            Self::InvalidDigit => f.write_str("invalid digit"),
+            Self::InvalidSuffix => f.write_str("invalid size suffix"),
+            Self::Unmapped => f.write_str("address is not mapped"),
+            Self::InvalidPageTable => f.write_str("invalid page table"),
+            Self::Overlap => f.write_str("overlapping entries"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn display() {
+        let error = Error::Misaligned {
+            address: 0x1001,
+            align: 8,
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "address 0x1001 is not aligned to 8 bytes"
+        );
+        assert_eq!(Error::Overflow.to_string(), "arithmetic overflow");
+    }
+}
@@ -5,6 +5,7 @@
 //!   * Addresses
 //!   * Offsets
 //!   * Pages
+//!   * Regions
 
 #![no_std]
 #![forbid(clippy::expect_used, clippy::panic)]
@@ -19,17 +20,111 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+/// Implements conversions between the integer widths of `Address` or `Offset`
+///
+/// Lossless conversions are implemented with `From`, which also provides
+/// `TryFrom` through the blanket impl. Lossy conversions are implemented
+/// with `TryFrom` and fail with `Error::OutOfRange`. Conversions between
+/// `usize` and the fixed widths which are lossless on the common targets are
+/// implemented by hand, so they are only listed here where they are lossy.
+macro_rules! implwidths {
+    ($name:ident) => {
+        implwidths! { @from $name:
+            u16 => u32, u16 => u64, u16 => u128, u16 => usize,
+            u32 => u64, u32 => u128,
+            u64 => u128,
+            usize => u128,
+        }
+
+        implwidths! { @try_from $name:
+            u32 => u16,
+            #[cfg(not(any(target_pointer_width = "32", target_pointer_width = "64")))] u32 => usize,
+            u64 => u16, u64 => u32,
+            #[cfg(not(target_pointer_width = "64"))] u64 => usize,
+            u128 => u16, u128 => u32, u128 => u64, u128 => usize,
+            usize => u16,
+            #[cfg(not(target_pointer_width = "32"))] usize => u32,
+            #[cfg(not(any(target_pointer_width = "32", target_pointer_width = "64")))] usize => u64,
+        }
+    };
+
+    (@from $name:ident: $($f:ident => $t:ident),+ $(,)?) => {
+        $(
+            impl<U> From<$name<$f, U>> for $name<$t, U> {
+                #[inline]
+                fn from(value: $name<$f, U>) -> Self {
+                    Self(value.0 as _, PhantomData)
+                }
+            }
+        )+
+    };
+
+    (@try_from $name:ident: $($(#[$attr:meta])? $f:ident => $t:ident),+ $(,)?) => {
+        $(
+            $(#[$attr])?
+            impl<U> TryFrom<$name<$f, U>> for $name<$t, U> {
+                type Error = Error;
+
+                #[inline]
+                fn try_from(value: $name<$f, U>) -> Result<Self, Self::Error> {
+                    match $t::try_from(value.0) {
+                        Ok(value) => Ok(Self(value, PhantomData)),
+                        Err(..) => Err(Error::OutOfRange),
+                    }
+                }
+            }
+        )+
+    };
+}
+
 mod address;
+mod alignment;
+mod canonical;
+mod encryption;
+mod error;
+mod mmio;
+mod nonnull;
 mod offset;
 mod page;
 mod pages;
+mod paging;
+mod parse;
+mod pointer;
+mod project;
+mod region;
+mod regions;
 mod register;
+mod relocation;
+mod slice;
+mod space;
+mod steps;
+mod tag;
+mod translate;
+mod volatile;
 
 pub use address::Address;
+pub use alignment::Alignment;
+pub use canonical::{CanonicalAddress, PagingMode};
+pub use encryption::EncryptionMask;
+pub use error::Error;
+pub use nonnull::{NonNullAddress, NonZeroInteger};
 pub use offset::Offset;
 pub use page::Page;
 pub use pages::Pages;
+pub use paging::{PageTableIndices, PageTableLayout};
+pub use pointer::PtrAddress;
+pub use region::Region;
+pub use regions::FixedRegionSet;
+#[cfg(feature = "alloc")]
+pub use regions::RegionSet;
 pub use register::Register;
+pub use relocation::Relocation;
+pub use slice::SliceAddress;
+pub use space::{AddressSpace, PhysAddr, Physical, SpaceAddress, VirtAddr, Virtual};
+pub use steps::Steps;
+pub use tag::TagMode;
+pub use translate::{OffsetMap, PageTableWalker, Permissions, SlotTable, Translate, Translation};
+pub use volatile::{Access, ReadOnly, ReadWrite, Readable, VolatileAddress, Writable, WriteOnly};
 
 /// Defines the additive identity value
 pub trait Zero: Copy {
@@ -43,6 +138,57 @@ pub trait One: Copy {
     const ONE: Self;
 }
 
+/// Defines arithmetic that reports overflow by returning `None`
+pub trait Checked: Sized {
+    /// Checked addition
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Checked subtraction
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    /// Checked multiplication
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    /// Checked division
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+}
+
+/// Defines arithmetic that reports overflow alongside the wrapped result
+pub trait Overflowing: Sized {
+    /// Overflowing addition
+    fn overflowing_add(self, rhs: Self) -> (Self, bool);
+
+    /// Overflowing subtraction
+    fn overflowing_sub(self, rhs: Self) -> (Self, bool);
+
+    /// Overflowing multiplication
+    fn overflowing_mul(self, rhs: Self) -> (Self, bool);
+}
+
+/// Defines arithmetic that wraps around at the boundary of the type
+pub trait Wrapping: Sized {
+    /// Wrapping addition
+    fn wrapping_add(self, rhs: Self) -> Self;
+
+    /// Wrapping subtraction
+    fn wrapping_sub(self, rhs: Self) -> Self;
+
+    /// Wrapping multiplication
+    fn wrapping_mul(self, rhs: Self) -> Self;
+}
+
+/// Defines arithmetic that saturates at the boundary of the type
+pub trait Saturating: Sized {
+    /// Saturating addition
+    fn saturating_add(self, rhs: Self) -> Self;
+
+    /// Saturating subtraction
+    fn saturating_sub(self, rhs: Self) -> Self;
+
+    /// Saturating multiplication
+    fn saturating_mul(self, rhs: Self) -> Self;
+}
+
 macro_rules! impltraits {
     ($($num:ty)+) => {
         $(
@@ -53,6 +199,79 @@ macro_rules! impltraits {
             impl One for $num {
                 const ONE: Self = 1;
             }
+
+            impl Checked for $num {
+                #[inline]
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$num>::checked_add(self, rhs)
+                }
+
+                #[inline]
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$num>::checked_sub(self, rhs)
+                }
+
+                #[inline]
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$num>::checked_mul(self, rhs)
+                }
+
+                #[inline]
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$num>::checked_div(self, rhs)
+                }
+            }
+
+            impl Overflowing for $num {
+                #[inline]
+                fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+                    <$num>::overflowing_add(self, rhs)
+                }
+
+                #[inline]
+                fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+                    <$num>::overflowing_sub(self, rhs)
+                }
+
+                #[inline]
+                fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+                    <$num>::overflowing_mul(self, rhs)
+                }
+            }
+
+            impl Wrapping for $num {
+                #[inline]
+                fn wrapping_add(self, rhs: Self) -> Self {
+                    <$num>::wrapping_add(self, rhs)
+                }
+
+                #[inline]
+                fn wrapping_sub(self, rhs: Self) -> Self {
+                    <$num>::wrapping_sub(self, rhs)
+                }
+
+                #[inline]
+                fn wrapping_mul(self, rhs: Self) -> Self {
+                    <$num>::wrapping_mul(self, rhs)
+                }
+            }
+
+            impl Saturating for $num {
+                #[inline]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$num>::saturating_add(self, rhs)
+                }
+
+                #[inline]
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    <$num>::saturating_sub(self, rhs)
+                }
+
+                #[inline]
+                fn saturating_mul(self, rhs: Self) -> Self {
+                    <$num>::saturating_mul(self, rhs)
+                }
+            }
         )+
     };
 }
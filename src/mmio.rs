@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: Apache-2.0
+
+/// Declares a block of memory-mapped registers
+///
+/// Each field is declared with its access mode (`ReadOnly`, `WriteOnly` or
+/// `ReadWrite`), its width and its byte offset from the start of the block.
+/// The generated type is placed on a single `Page` and has one accessor per
+/// field, which returns a `VolatileAddress` to a `Register` of that width.
+/// Offsets are checked at compile time: every field must be aligned to its
+/// width and must fit within the page.
+///
+/// ```
+/// use primordial::{register_block, Address, Page, Register};
+///
+/// register_block! {
+///     /// A serial port
+///     pub struct Serial {
+///         /// Transmit and receive buffer
+///         pub data: ReadWrite<u32> = 0x00,
+///
+///         /// Line status
+///         pub status: ReadOnly<u32> = 0x14,
+///     }
+/// }
+///
+/// let mut page = Page::zeroed();
+/// let addr = Address::from(&mut page as *mut Page);
+/// let serial = unsafe { Serial::new(addr) };
+///
+/// serial.data().write(Register::from(b'x' as u32));
+/// assert_eq!(serial.status().read(), Register::from(0u32));
+/// assert_eq!(page[0], b'x');
+/// ```
+///
+/// A misaligned register is rejected at compile time:
+///
+/// ```compile_fail
+/// primordial::register_block! {
+///     struct Broken {
+///         reg: ReadWrite<u32> = 0x02,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! register_block {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$fattr:meta])*
+                $fvis:vis $field:ident: $access:ident<$ty:ty> = $offset:expr
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug)]
+        $vis struct $name($crate::PtrAddress<$crate::Page>);
+
+        impl $name {
+            /// Places the register block at `base`
+            ///
+            /// The pointer is created with exposed provenance.
+            ///
+            /// # Safety
+            ///
+            /// The caller MUST ensure that `base` is valid for volatile
+            /// accesses of every register in the block for as long as the
+            /// value or any copy of it is in use.
+            #[inline]
+            #[allow(dead_code)]
+            $vis unsafe fn new(base: $crate::Address<usize, $crate::Page>) -> Self {
+                Self($crate::PtrAddress::unchecked(base.as_mut_ptr()))
+            }
+
+            /// Places the register block at `base`
+            ///
+            /// # Safety
+            ///
+            /// The caller MUST ensure that `base` is valid for volatile
+            /// accesses of every register in the block for as long as the
+            /// value or any copy of it is in use.
+            #[inline]
+            #[allow(dead_code)]
+            $vis const unsafe fn from_ptr(base: $crate::PtrAddress<$crate::Page>) -> Self {
+                Self(base)
+            }
+
+            /// Returns the address of the register block
+            #[inline]
+            #[allow(dead_code)]
+            $vis fn base(&self) -> $crate::Address<usize, $crate::Page> {
+                self.0.address()
+            }
+
+            $(
+                $(#[$fattr])*
+                #[inline]
+                #[allow(dead_code)]
+                $fvis fn $field(&self) -> $crate::VolatileAddress<$crate::Register<$ty>, $crate::$access> {
+                    const OFFSET: $crate::Offset<usize, u8> = $crate::Offset::from_items($offset);
+
+                    let addr = self.0.address().raw() + OFFSET.const_items();
+                    unsafe {
+                        let addr = $crate::Address::unchecked(addr);
+                        $crate::VolatileAddress::from_ptr(self.0.with_address(addr))
+                    }
+                }
+            )*
+        }
+
+        const _: () = {
+            $(
+                assert!(
+                    $offset % ::core::mem::align_of::<$ty>() == 0,
+                    concat!("misaligned register: ", stringify!($field))
+                );
+                assert!(
+                    $offset + ::core::mem::size_of::<$ty>() <= $crate::Page::SIZE,
+                    concat!("register outside of the page: ", stringify!($field))
+                );
+            )*
+        };
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    register_block! {
+        /// A fake device
+        struct Device {
+            /// Identification
+            id: ReadOnly<u32> = 0x00,
+
+            /// Doorbell
+            doorbell: WriteOnly<u32> = 0x04,
+
+            /// Ring address
+            ring: ReadWrite<u64> = 0x08,
+
+            /// End of interrupt
+            eoi: WriteOnly<u32> = 0xb0,
+        }
+    }
+
+    #[test]
+    fn block() {
+        let mut page = Page::zeroed();
+        page[..4].copy_from_slice(&0x1af4u32.to_ne_bytes());
+
+        let addr = Address::from(&mut page as *mut Page);
+        let device = unsafe { Device::new(addr) };
+        assert_eq!(device.base(), addr);
+
+        assert_eq!(device.id().read(), Register::from(0x1af4u32));
+        device.doorbell().write(Register::from(1u32));
+        device.ring().write(Register::from(0xdead_b000u64));
+        device.ring().update(|r| Register::from(u64::from(r) | 1));
+        device.eoi().write(Register::from(0u32));
+
+        assert_eq!(device.ring().address().raw() - addr.raw(), 0x08);
+        assert_eq!(page[4..8], 1u32.to_ne_bytes());
+        assert_eq!(page[8..16], 0xdead_b001u64.to_ne_bytes());
+    }
+}
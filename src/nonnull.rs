@@ -0,0 +1,241 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::ptr::NonNull;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// An integer type with a non-zero counterpart
+///
+/// This trait is sealed and is implemented for all unsigned integer types.
+pub trait NonZeroInteger: sealed::Sealed + Copy {
+    /// The non-zero counterpart of this type
+    type NonZero: Copy + Eq + Ord + Hash;
+
+    /// Converts the value to its non-zero counterpart
+    ///
+    /// Returns `None` if the value is zero.
+    fn non_zero(self) -> Option<Self::NonZero>;
+
+    /// Converts the non-zero counterpart back to the value
+    fn get(value: Self::NonZero) -> Self;
+}
+
+macro_rules! implnonzero {
+    ($($num:ident)+) => {
+        $(
+            impl sealed::Sealed for $num {}
+
+            impl NonZeroInteger for $num {
+                type NonZero = core::num::NonZero<$num>;
+
+                #[inline]
+                fn non_zero(self) -> Option<Self::NonZero> {
+                    Self::NonZero::new(self)
+                }
+
+                #[inline]
+                fn get(value: Self::NonZero) -> Self {
+                    value.get()
+                }
+            }
+        )+
+    };
+}
+
+implnonzero! { u8 u16 u32 u64 u128 usize }
+
+/// An address that is guaranteed not to be null
+///
+/// This type offers the same alignment guarantee as `Address<T, U>`, but it
+/// is built on the `NonZero` integer types. This allows the compiler to use
+/// the null value as a niche, so `Option<NonNullAddress<T, U>>` has the same
+/// size as `NonNullAddress<T, U>`.
+#[repr(transparent)]
+pub struct NonNullAddress<T: NonZeroInteger, U>(T::NonZero, PhantomData<U>);
+
+impl<T: NonZeroInteger, U> Clone for NonNullAddress<T, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: NonZeroInteger, U> Copy for NonNullAddress<T, U> {}
+
+impl<T: NonZeroInteger + core::fmt::LowerHex, U> core::fmt::Debug for NonNullAddress<T, U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "NonNullAddress(0x{:01$x})",
+            T::get(self.0),
+            size_of::<T>() * 2
+        ))
+    }
+}
+
+impl<T: NonZeroInteger, U> PartialEq for NonNullAddress<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: NonZeroInteger, U> Eq for NonNullAddress<T, U> {}
+
+impl<T: NonZeroInteger, U> Hash for NonNullAddress<T, U> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<T: NonZeroInteger, U> PartialOrd for NonNullAddress<T, U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: NonZeroInteger, U> Ord for NonNullAddress<T, U> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T: NonZeroInteger, U> NonNullAddress<T, U> {
+    /// Creates a new `NonNullAddress` from an `Address`
+    ///
+    /// Returns `None` if the address is null.
+    #[inline]
+    pub fn new(addr: Address<T, U>) -> Option<Self> {
+        Some(Self(addr.raw().non_zero()?, PhantomData))
+    }
+
+    /// Creates a new `NonNullAddress` from an `Address` without checking
+    ///
+    /// # Safety
+    ///
+    /// The address must not be null.
+    #[inline]
+    pub unsafe fn unchecked(addr: Address<T, U>) -> Self {
+        match addr.raw().non_zero() {
+            Some(value) => Self(value, PhantomData),
+            None => core::hint::unreachable_unchecked(),
+        }
+    }
+
+    /// Returns the address
+    #[inline]
+    pub fn address(self) -> Address<T, U> {
+        unsafe { Address::unchecked(T::get(self.0)) }
+    }
+
+    /// Returns the raw inner value
+    #[inline]
+    pub fn raw(self) -> T {
+        T::get(self.0)
+    }
+}
+
+impl<T: NonZeroInteger, U> NonNullAddress<T, U>
+where
+    Address<T, U>: Into<Address<usize, U>>,
+{
+    /// Converts the address to a `NonNull` pointer
+    ///
+    /// The pointer is created with exposed provenance.
+    #[inline]
+    pub fn as_non_null(self) -> NonNull<U> {
+        let addr = self.address().into().raw();
+
+        // The conversion to usize preserves the value, so it is not zero.
+        unsafe { NonNull::new_unchecked(core::ptr::with_exposed_provenance_mut(addr)) }
+    }
+}
+
+impl<T: NonZeroInteger, U> TryFrom<Address<T, U>> for NonNullAddress<T, U> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: Address<T, U>) -> Result<Self, Self::Error> {
+        Self::new(value).ok_or(Error::Null)
+    }
+}
+
+impl<T: NonZeroInteger, U> From<NonNullAddress<T, U>> for Address<T, U> {
+    #[inline]
+    fn from(value: NonNullAddress<T, U>) -> Self {
+        value.address()
+    }
+}
+
+impl<T: NonZeroInteger, U> From<NonNull<U>> for NonNullAddress<T, U>
+where
+    Address<usize, U>: Into<Address<T, U>>,
+{
+    #[inline]
+    fn from(value: NonNull<U>) -> Self {
+        let addr: Address<T, U> = Address::from(value.as_ptr()).into();
+
+        // The conversion from usize preserves the value, so it is not zero.
+        unsafe { Self::unchecked(addr) }
+    }
+}
+
+impl<T: NonZeroInteger, U> From<NonNullAddress<T, U>> for NonNull<U>
+where
+    Address<T, U>: Into<Address<usize, U>>,
+{
+    #[inline]
+    fn from(value: NonNullAddress<T, U>) -> Self {
+        value.as_non_null()
+    }
+}
+
+impl<T: NonZeroInteger, U> From<&U> for NonNullAddress<T, U>
+where
+    Address<usize, U>: Into<Address<T, U>>,
+{
+    #[inline]
+    fn from(value: &U) -> Self {
+        NonNull::from(value).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn niche() {
+        assert_eq!(
+            size_of::<Option<NonNullAddress<usize, u64>>>(),
+            size_of::<usize>()
+        );
+        assert_eq!(size_of::<Option<NonNullAddress<u32, u8>>>(), 4);
+    }
+
+    #[test]
+    fn convert() {
+        let null: Address<u64, u32> = Address::NULL;
+        assert_eq!(NonNullAddress::try_from(null), Err(Error::Null));
+
+        let addr = Address::from(0x1000u64).lower::<u32>();
+        let nn = NonNullAddress::try_from(addr).unwrap();
+        assert_eq!(nn.raw(), 0x1000);
+        assert_eq!(Address::from(nn), addr);
+    }
+
+    #[test]
+    fn pointer() {
+        let value = 7u16;
+        let nn = NonNullAddress::<usize, u16>::from(&value);
+        let ptr: NonNull<u16> = nn.into();
+
+        assert_eq!(ptr.as_ptr() as *const u16, &value as *const u16);
+        assert_eq!(unsafe { *ptr.as_ptr() }, 7);
+        assert_eq!(NonNullAddress::<u64, u16>::from(ptr).raw(), nn.raw() as u64);
+    }
+}
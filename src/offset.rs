@@ -2,6 +2,7 @@
 
 use super::*;
 use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
 use core::marker::PhantomData;
 use core::mem::size_of;
 use core::ops::*;
@@ -57,6 +58,12 @@ impl<T: PartialEq, U> PartialEq for Offset<T, U> {
 
 impl<T: Eq, U> Eq for Offset<T, U> {}
 
+impl<T: Hash, U> Hash for Offset<T, U> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
 impl<T: PartialOrd, U> PartialOrd for Offset<T, U> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.0.partial_cmp(&other.0)
@@ -88,6 +95,31 @@ impl<T, U> Offset<T, U> {
     }
 }
 
+macro_rules! implconst {
+    ($($t:ident)+) => {
+        $(
+            impl<U> Offset<$t, U> {
+                /// Get the number of items in a `const` context
+                #[inline]
+                pub const fn const_items(self) -> $t {
+                    self.0
+                }
+
+                /// Get the number of bytes in a `const` context
+                ///
+                /// Overflow is a compile-time error when evaluated in a
+                /// `const` or `static` item.
+                #[inline]
+                pub const fn const_bytes(self) -> $t {
+                    self.0 * size_of::<U>() as $t
+                }
+            }
+        )+
+    };
+}
+
+implconst! { usize u64 }
+
 impl<T, U> Offset<T, U>
 where
     Offset<usize, ()>: Into<Offset<T, ()>>,
@@ -100,6 +132,273 @@ where
     }
 }
 
+impl<T, U> Offset<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Checked,
+{
+    /// Get the number of bytes
+    ///
+    /// Returns `None` if the number of bytes overflows `T`.
+    #[inline]
+    pub fn checked_bytes(self) -> Option<T> {
+        self.0
+            .checked_mul(Offset(size_of::<U>(), PhantomData).into().items())
+    }
+}
+
+impl<T, U> Offset<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + PartialEq + Zero + One,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+{
+    /// Splits a number of bytes into whole items and remaining bytes
+    ///
+    /// A zero-sized `U` is a compile-time error.
+    #[inline]
+    fn split_bytes(bytes: T) -> (T, T) {
+        const { assert!(size_of::<U>() != 0, "zero-sized item type") }
+
+        let size: T = Offset(size_of::<U>(), PhantomData).into().items();
+        let items = bytes / size;
+        (items, bytes - items * size)
+    }
+
+    /// Create an offset value from a number of bytes
+    ///
+    /// Fails with `Error::Inexact` if the bytes are not a whole number of
+    /// items.
+    #[inline]
+    pub fn from_bytes_exact(bytes: T) -> Result<Self, Error> {
+        match Self::split_bytes(bytes) {
+            (items, rem) if rem == T::ZERO => Ok(Self(items, PhantomData)),
+            _ => Err(Error::Inexact),
+        }
+    }
+
+    /// Create an offset value from a number of bytes, rounding down
+    ///
+    /// This is the number of whole items which fit in the bytes.
+    #[inline]
+    pub fn from_bytes_floor(bytes: T) -> Self {
+        Self(Self::split_bytes(bytes).0, PhantomData)
+    }
+
+    /// Create an offset value from a number of bytes, rounding up
+    ///
+    /// This is the number of items needed to hold the bytes.
+    #[inline]
+    pub fn from_bytes_ceil(bytes: T) -> Self {
+        match Self::split_bytes(bytes) {
+            (items, rem) if rem == T::ZERO => Self(items, PhantomData),
+            (items, _) => Self(items + T::ONE, PhantomData),
+        }
+    }
+}
+
+/// Returns the greatest common divisor of two item sizes
+const fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+impl<T, U> Offset<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + PartialEq + Zero + One + Checked,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+{
+    /// Converts the offset to a number of items of type `V`
+    ///
+    /// Returns the whole number of `V` items and whether there was a
+    /// remainder. The items are scaled by the ratio of the item sizes,
+    /// reduced at compile time, without going through the number of bytes.
+    #[inline]
+    fn convert<V>(self) -> Result<(Offset<T, V>, bool), Error> {
+        const {
+            assert!(
+                size_of::<U>() != 0 && size_of::<V>() != 0,
+                "zero-sized item type"
+            )
+        }
+
+        let gcd = const { gcd(size_of::<U>(), size_of::<V>()) };
+        let num = Offset(size_of::<U>() / gcd, PhantomData).into().items();
+        let den = Offset(size_of::<V>() / gcd, PhantomData).into().items();
+
+        // items * num / den, split so that items * num is never formed.
+        let quot = self.0 / den;
+        let part = (self.0 - quot * den)
+            .checked_mul(num)
+            .ok_or(Error::Overflow)?;
+        let whole = quot.checked_mul(num).ok_or(Error::Overflow)?;
+        let items = whole.checked_add(part / den).ok_or(Error::Overflow)?;
+        let rem = part - part / den * den;
+        Ok((Offset(items, PhantomData), rem != T::ZERO))
+    }
+
+    /// Converts the offset to a number of items of type `V`
+    ///
+    /// Fails with `Error::Inexact` if the offset is not a whole number of
+    /// `V` items, or with `Error::Overflow` if the result does not fit in
+    /// `T`.
+    ///
+    /// When the size of `U` is a multiple of the size of `V` the conversion
+    /// can never be inexact, which is known at compile time; it can then
+    /// only fail with `Error::Overflow`. A zero-sized `U` or `V` is a
+    /// compile-time error.
+    ///
+    /// ```
+    /// use primordial::{Error, Offset, Page};
+    ///
+    /// let pages: Offset<usize, Page> = Offset::from_items(2);
+    /// assert_eq!(pages.cast::<u64>(), Ok(Offset::from_items(1024)));
+    ///
+    /// let bytes: Offset<usize, u8> = Offset::from_items(4097);
+    /// assert_eq!(bytes.cast::<Page>(), Err(Error::Inexact));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use primordial::Offset;
+    ///
+    /// let units: Offset<usize, ()> = Offset::from_items(4);
+    /// let bytes = units.cast::<u8>();
+    /// ```
+    #[inline]
+    pub fn cast<V>(self) -> Result<Offset<T, V>, Error> {
+        match self.convert()? {
+            (offset, false) => Ok(offset),
+            (_, true) => Err(Error::Inexact),
+        }
+    }
+
+    /// Converts the offset to a number of items of type `V`, rounding up
+    ///
+    /// Fails with `Error::Overflow` if the result does not fit in `T`.
+    #[inline]
+    pub fn cast_ceil<V>(self) -> Result<Offset<T, V>, Error> {
+        match self.convert()? {
+            (offset, false) => Ok(offset),
+            (offset, true) => match offset.0.checked_add(T::ONE) {
+                Some(items) => Ok(Offset(items, PhantomData)),
+                None => Err(Error::Overflow),
+            },
+        }
+    }
+}
+
+impl<T: Checked, U> Offset<T, U> {
+    /// Checked addition
+    ///
+    /// Returns `None` if the number of items overflows.
+    #[inline]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self::from_items)
+    }
+
+    /// Checked subtraction
+    ///
+    /// Returns `None` if the number of items overflows.
+    #[inline]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self::from_items)
+    }
+
+    /// Checked multiplication
+    ///
+    /// Returns `None` if the number of items overflows.
+    #[inline]
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.0.checked_mul(rhs.0).map(Self::from_items)
+    }
+
+    /// Checked division
+    ///
+    /// Returns `None` if `rhs` is zero.
+    #[inline]
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.0.checked_div(rhs.0).map(Self::from_items)
+    }
+}
+
+impl<T: Overflowing, U> Offset<T, U> {
+    /// Overflowing addition
+    ///
+    /// Returns the wrapped result and whether an overflow occurred.
+    #[inline]
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let (items, overflow) = self.0.overflowing_add(rhs.0);
+        (Self::from_items(items), overflow)
+    }
+
+    /// Overflowing subtraction
+    ///
+    /// Returns the wrapped result and whether an overflow occurred.
+    #[inline]
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let (items, overflow) = self.0.overflowing_sub(rhs.0);
+        (Self::from_items(items), overflow)
+    }
+
+    /// Overflowing multiplication
+    ///
+    /// Returns the wrapped result and whether an overflow occurred.
+    #[inline]
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        let (items, overflow) = self.0.overflowing_mul(rhs.0);
+        (Self::from_items(items), overflow)
+    }
+}
+
+impl<T: Wrapping, U> Offset<T, U> {
+    /// Wrapping addition
+    #[inline]
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        Self::from_items(self.0.wrapping_add(rhs.0))
+    }
+
+    /// Wrapping subtraction
+    #[inline]
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        Self::from_items(self.0.wrapping_sub(rhs.0))
+    }
+
+    /// Wrapping multiplication
+    #[inline]
+    pub fn wrapping_mul(self, rhs: Self) -> Self {
+        Self::from_items(self.0.wrapping_mul(rhs.0))
+    }
+}
+
+impl<T: Saturating, U> Offset<T, U> {
+    /// Saturating addition
+    #[inline]
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self::from_items(self.0.saturating_add(rhs.0))
+    }
+
+    /// Saturating subtraction
+    #[inline]
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self::from_items(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Saturating multiplication
+    #[inline]
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        Self::from_items(self.0.saturating_mul(rhs.0))
+    }
+}
+
 impl<T: Zero, U: Copy> Zero for Offset<T, U> {
     const ZERO: Offset<T, U> = Offset::from_items(T::ZERO);
 }
@@ -157,6 +456,8 @@ impl<U> From<Offset<usize, U>> for Offset<u32, U> {
     }
 }
 
+implwidths!(Offset);
+
 impl<T: Add<T, Output = T>, U> Add for Offset<T, U> {
     type Output = Self;
 
@@ -236,3 +537,123 @@ impl<T: SubAssign<T>, U> SubAssign for Offset<T, U> {
         self.0 -= rhs.0;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn checked_bytes() {
+        assert_eq!(
+            Offset::<usize, u32>::from_items(3).checked_bytes(),
+            Some(12)
+        );
+        assert_eq!(
+            Offset::<usize, Page>::from_items(usize::MAX).checked_bytes(),
+            None
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let max = Offset::<u64, u8>::from_items(u64::MAX);
+        let one = Offset::<u64, u8>::from_items(1);
+
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(one.checked_sub(max), None);
+        assert_eq!(max.overflowing_add(one), (Offset::from_items(0), true));
+        assert_eq!(max.wrapping_add(one), Offset::from_items(0));
+        assert_eq!(max.saturating_add(one), max);
+        assert_eq!(one.saturating_sub(max), Offset::from_items(0));
+    }
+
+    #[test]
+    fn try_from() {
+        let offset = Offset::<u64, u16>::from_items(0x1_0000);
+        assert_eq!(Offset::<u16, u16>::try_from(offset), Err(Error::OutOfRange));
+        assert_eq!(
+            Offset::<u32, u16>::try_from(offset).unwrap().items(),
+            0x1_0000
+        );
+
+        let offset = Offset::<usize, u16>::from_items(7);
+        assert_eq!(Offset::<u128, u16>::from(offset).items(), 7);
+        assert_eq!(Offset::<u32, u16>::try_from(offset).unwrap().items(), 7);
+    }
+
+    #[test]
+    fn from_bytes() {
+        assert_eq!(
+            Offset::<usize, Page>::from_bytes_ceil(5000),
+            Offset::from_items(2)
+        );
+        assert_eq!(
+            Offset::<usize, Page>::from_bytes_floor(5000),
+            Offset::from_items(1)
+        );
+        assert_eq!(
+            Offset::<usize, Page>::from_bytes_exact(5000),
+            Err(Error::Inexact)
+        );
+        assert_eq!(
+            Offset::<u64, u32>::from_bytes_exact(16),
+            Ok(Offset::from_items(4))
+        );
+        assert_eq!(
+            Offset::<u64, u32>::from_bytes_ceil(u64::MAX),
+            Offset::from_items(1 << 62)
+        );
+        assert_eq!(
+            Offset::<usize, u64>::from_bytes_ceil(0),
+            Offset::from_items(0)
+        );
+    }
+
+    #[test]
+    fn cast() {
+        let bytes = Offset::<usize, u8>::from_items(8192);
+        assert_eq!(bytes.cast::<Page>(), Ok(Offset::from_items(2)));
+
+        let bytes = Offset::<usize, u8>::from_items(8193);
+        assert_eq!(bytes.cast::<Page>(), Err(Error::Inexact));
+        assert_eq!(bytes.cast_ceil::<Page>(), Ok(Offset::from_items(3)));
+
+        let words = Offset::<u64, u32>::from_items(3);
+        assert_eq!(words.cast::<u8>(), Ok(Offset::from_items(12)));
+        assert_eq!(words.cast::<u64>(), Err(Error::Inexact));
+        assert_eq!(words.cast::<u16>(), Ok(Offset::from_items(6)));
+
+        let pages = Offset::<u64, Page>::from_items(u64::MAX);
+        assert_eq!(pages.cast::<u8>(), Err(Error::Overflow));
+        assert_eq!(pages.cast_ceil::<u64>(), Err(Error::Overflow));
+
+        let words = Offset::<u64, u32>::from_items(u64::MAX / 2);
+        assert_eq!(words.cast::<Page>(), Err(Error::Inexact));
+        assert_eq!(words.cast_ceil::<Page>(), Ok(Offset::from_items(1 << 53)));
+
+        let words = Offset::<u64, u32>::from_items(u64::MAX / 1024 * 1024);
+        assert_eq!(
+            words.cast::<Page>(),
+            Ok(Offset::from_items(u64::MAX / 1024))
+        );
+
+        let triples = Offset::<u64, [u32; 3]>::from_items(u64::MAX / 3);
+        assert_eq!(triples.cast::<u64>(), Err(Error::Inexact));
+        assert_eq!(triples.cast_ceil::<u64>(), Ok(Offset::from_items(1 << 63)));
+
+        let triples = Offset::<u64, [u32; 3]>::from_items(u64::MAX / 3 - 1);
+        assert_eq!(
+            triples.cast::<u64>(),
+            Ok(Offset::from_items(u64::MAX / 2 - 1))
+        );
+        assert_eq!(triples.cast::<[u8; 9]>(), Err(Error::Inexact));
+        assert_eq!(
+            Offset::<u64, [u32; 3]>::from_items(3).cast::<[u8; 9]>(),
+            Ok(Offset::from_items(4))
+        );
+        assert_eq!(
+            Offset::<u64, [u8; 9]>::from_items(u64::MAX / 3 * 2 + 1).cast_ceil::<[u32; 3]>(),
+            Ok(Offset::from_items((1 << 63) + 1))
+        );
+    }
+}
@@ -38,6 +38,7 @@ impl Pages<alloc::vec::Vec<Page>> {
         let data = &data[..core::cmp::min(size, data.len())];
 
         // Allocate a buffer large enough for offset + size.
+        #[allow(clippy::manual_div_ceil)]
         let count = (offset + size + Page::SIZE - 1) / Page::SIZE;
         let mut buf = alloc::vec::Vec::with_capacity(count);
         let bytes: &mut [u8] = unsafe {
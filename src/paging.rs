@@ -0,0 +1,359 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// The maximum number of levels supported by `PageTableLayout`
+const MAX_LEVELS: usize = 5;
+
+/// The layout of a multi-level page table
+///
+/// A layout describes how a virtual address is split into page table indices
+/// and a page offset. The lowest `page_shift` bits are the offset into the
+/// page. Each level above consumes `index_bits` bits, except for the top
+/// level, which consumes whatever remains of the `address_bits` bits.
+///
+/// Levels are numbered from the leaf upwards: level 0 is the table holding
+/// the final page mappings (the x86-64 PT, aarch64 level 3 or RISC-V
+/// `VPN[0]`).
+///
+/// A layout also records whether reassembled addresses are sign-extended
+/// from the most significant address bit. x86-64 and RISC-V require this.
+/// On aarch64 it depends on the translation table base register in use, so
+/// the aarch64 layouts describe the TTBR0 (lower) half and do not extend.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PageTableLayout {
+    page_shift: u32,
+    index_bits: u32,
+    address_bits: u32,
+    sign_extend: bool,
+}
+
+impl PageTableLayout {
+    /// x86-64 4-level paging with 4 KiB pages
+    pub const X86_64_LEVEL4: Self = Self::raw(12, 9, 48, true);
+
+    /// x86-64 5-level paging with 4 KiB pages
+    pub const X86_64_LEVEL5: Self = Self::raw(12, 9, 57, true);
+
+    /// aarch64 with a 4 KiB granule and 48-bit virtual addresses
+    pub const AARCH64_4K: Self = Self::raw(12, 9, 48, false);
+
+    /// aarch64 with a 16 KiB granule and 48-bit virtual addresses
+    pub const AARCH64_16K: Self = Self::raw(14, 11, 48, false);
+
+    /// aarch64 with a 64 KiB granule and 48-bit virtual addresses
+    pub const AARCH64_64K: Self = Self::raw(16, 13, 48, false);
+
+    /// RISC-V Sv39
+    pub const RISCV_SV39: Self = Self::raw(12, 9, 39, true);
+
+    /// RISC-V Sv48
+    pub const RISCV_SV48: Self = Self::raw(12, 9, 48, true);
+
+    /// RISC-V Sv57
+    pub const RISCV_SV57: Self = Self::raw(12, 9, 57, true);
+
+    const fn raw(page_shift: u32, index_bits: u32, address_bits: u32, sign_extend: bool) -> Self {
+        Self {
+            page_shift,
+            index_bits,
+            address_bits,
+            sign_extend,
+        }
+    }
+
+    /// Creates a custom layout
+    ///
+    /// The layout does not sign-extend reassembled addresses; use
+    /// `with_sign_extension()` to change that. Fails with
+    /// `Error::InvalidSize` if the parameters do not describe between one and
+    /// five levels within a 64-bit address.
+    #[inline]
+    pub const fn new(page_shift: u32, index_bits: u32, address_bits: u32) -> Result<Self, Error> {
+        if index_bits == 0 || address_bits > 64 || page_shift >= address_bits {
+            return Err(Error::InvalidSize);
+        }
+
+        let layout = Self::raw(page_shift, index_bits, address_bits, false);
+        match layout.levels() <= MAX_LEVELS && index_bits <= 16 {
+            true => Ok(layout),
+            false => Err(Error::InvalidSize),
+        }
+    }
+
+    /// Returns the number of levels of page tables
+    #[inline]
+    #[allow(clippy::manual_div_ceil)]
+    pub const fn levels(self) -> usize {
+        let bits = self.address_bits - self.page_shift;
+        ((bits + self.index_bits - 1) / self.index_bits) as usize
+    }
+
+    /// Returns the number of significant bits in a virtual address
+    #[inline]
+    pub const fn address_bits(self) -> u32 {
+        self.address_bits
+    }
+
+    /// Returns whether reassembled addresses are sign-extended
+    #[inline]
+    pub const fn sign_extend(self) -> bool {
+        self.sign_extend
+    }
+
+    /// Returns the same layout with sign extension enabled or disabled
+    ///
+    /// For example, aarch64 addresses translated through TTBR1 (the upper
+    /// half) are sign-extended, while TTBR0 addresses are not.
+    #[inline]
+    pub const fn with_sign_extension(self, sign_extend: bool) -> Self {
+        Self {
+            sign_extend,
+            ..self
+        }
+    }
+
+    /// Returns the size of the smallest page in bytes
+    #[inline]
+    pub const fn page_size(self) -> u64 {
+        1 << self.page_shift
+    }
+
+    /// Returns the shift of the first address bit indexing the given level
+    #[inline]
+    pub const fn shift(self, level: usize) -> u32 {
+        self.page_shift + level as u32 * self.index_bits
+    }
+
+    /// Returns the number of entries in a table at the given level
+    ///
+    /// The top-level table may have fewer entries than the other levels.
+    /// Returns zero if the level does not exist.
+    #[inline]
+    pub const fn entries(self, level: usize) -> usize {
+        if level >= self.levels() {
+            return 0;
+        }
+
+        let bits = self.address_bits - self.shift(level);
+        match bits < self.index_bits {
+            true => 1 << bits,
+            false => 1 << self.index_bits,
+        }
+    }
+}
+
+impl From<PagingMode> for PageTableLayout {
+    #[inline]
+    fn from(value: PagingMode) -> Self {
+        match value {
+            PagingMode::Level4 => Self::X86_64_LEVEL4,
+            PagingMode::Level5 => Self::X86_64_LEVEL5,
+        }
+    }
+}
+
+/// The page table indices and page offset of a virtual address
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PageTableIndices {
+    layout: PageTableLayout,
+    indices: [u16; MAX_LEVELS],
+    offset: u64,
+}
+
+impl PageTableIndices {
+    /// Creates indices from their components
+    ///
+    /// The `indices` slice is indexed by level, starting with the leaf. It
+    /// must contain exactly one valid index per level. Fails with
+    /// `Error::InvalidSize` if the number of indices does not match the
+    /// layout, or with `Error::OutOfRange` if any index or the offset is out
+    /// of range.
+    #[inline]
+    pub fn new(layout: PageTableLayout, indices: &[usize], offset: u64) -> Result<Self, Error> {
+        if indices.len() != layout.levels() {
+            return Err(Error::InvalidSize);
+        }
+
+        if offset >= layout.page_size() {
+            return Err(Error::OutOfRange);
+        }
+
+        let mut all = [0; MAX_LEVELS];
+        for (level, index) in indices.iter().enumerate() {
+            if *index >= layout.entries(level) {
+                return Err(Error::OutOfRange);
+            }
+
+            all[level] = *index as u16;
+        }
+
+        Ok(Self {
+            layout,
+            indices: all,
+            offset,
+        })
+    }
+
+    /// Returns the layout used to decompose the address
+    #[inline]
+    pub fn layout(&self) -> PageTableLayout {
+        self.layout
+    }
+
+    /// Returns the index into the table at the given level
+    ///
+    /// Returns `None` if the level does not exist.
+    #[inline]
+    pub fn index(&self, level: usize) -> Option<usize> {
+        match level < self.layout.levels() {
+            true => Some(self.indices[level] as usize),
+            false => None,
+        }
+    }
+
+    /// Iterates over the indices in walk order, from the top level down
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.indices[..self.layout.levels()]
+            .iter()
+            .rev()
+            .map(|index| *index as usize)
+    }
+
+    /// Returns the offset into the page
+    #[inline]
+    pub fn offset(&self) -> Offset<u64, u8> {
+        Offset::from_items(self.offset)
+    }
+
+    /// Reassembles the virtual address
+    ///
+    /// The result is sign-extended from the most significant address bit if
+    /// the layout requires it (see `PageTableLayout::sign_extend()`).
+    #[inline]
+    pub fn address(&self) -> Address<u64, ()> {
+        let mut value = self.offset;
+        for level in 0..self.layout.levels() {
+            value |= (self.indices[level] as u64) << self.layout.shift(level);
+        }
+
+        if !self.layout.sign_extend {
+            return Address::from(value);
+        }
+
+        let shift = 64 - self.layout.address_bits;
+        Address::from(((value << shift) as i64 >> shift) as u64)
+    }
+}
+
+impl<U> Address<u64, U> {
+    /// Returns the index into the page table at the given level
+    ///
+    /// Returns `None` if the level does not exist in the layout.
+    #[inline]
+    pub fn page_table_index(self, layout: PageTableLayout, level: usize) -> Option<usize> {
+        match layout.entries(level) {
+            0 => None,
+            n => Some((self.raw() >> layout.shift(level)) as usize & (n - 1)),
+        }
+    }
+
+    /// Splits the address into page table indices and a page offset
+    ///
+    /// Bits above the layout's address bits are ignored.
+    #[inline]
+    pub fn page_table_indices(self, layout: PageTableLayout) -> PageTableIndices {
+        let value = self.raw();
+
+        let mut indices = [0; MAX_LEVELS];
+        for (level, index) in indices.iter_mut().enumerate().take(layout.levels()) {
+            let mask = layout.entries(level) as u64 - 1;
+            *index = (value >> layout.shift(level) & mask) as u16;
+        }
+
+        PageTableIndices {
+            layout,
+            indices,
+            offset: value & (layout.page_size() - 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn layouts() {
+        assert_eq!(PageTableLayout::X86_64_LEVEL4.levels(), 4);
+        assert_eq!(PageTableLayout::X86_64_LEVEL5.levels(), 5);
+        assert_eq!(PageTableLayout::AARCH64_16K.levels(), 4);
+        assert_eq!(PageTableLayout::AARCH64_16K.entries(3), 2);
+        assert_eq!(PageTableLayout::AARCH64_64K.levels(), 3);
+        assert_eq!(PageTableLayout::AARCH64_64K.entries(2), 64);
+        assert_eq!(PageTableLayout::RISCV_SV39.levels(), 3);
+        assert_eq!(PageTableLayout::RISCV_SV39.entries(3), 0);
+        assert_eq!(PageTableLayout::new(12, 9, 66), Err(Error::InvalidSize));
+        assert_eq!(PageTableLayout::new(12, 3, 48), Err(Error::InvalidSize));
+    }
+
+    #[test]
+    fn x86_64() {
+        let layout = PageTableLayout::from(PagingMode::Level4);
+        let addr = Address::<u64, ()>::from(0xffff_8812_3456_7abc);
+        let indices = addr.page_table_indices(layout);
+
+        assert_eq!(indices.index(3), Some(0x110));
+        assert_eq!(indices.index(2), Some(0x048));
+        assert_eq!(indices.index(1), Some(0x1a2));
+        assert_eq!(indices.index(0), Some(0x167));
+        assert_eq!(indices.index(4), None);
+        assert_eq!(indices.offset(), Offset::from_items(0xabc));
+        assert_eq!(addr.page_table_index(layout, 1), Some(0x1a2));
+
+        let walk = [0x110, 0x048, 0x1a2, 0x167];
+        assert!(indices.iter().eq(walk.iter().copied()));
+        assert_eq!(indices.address(), addr);
+
+        let built = PageTableIndices::new(layout, &[0x167, 0x1a2, 0x048, 0x110], 0xabc).unwrap();
+        assert_eq!(built, indices);
+    }
+
+    #[test]
+    fn aarch64() {
+        let layout = PageTableLayout::AARCH64_64K;
+        let addr = Address::<u64, ()>::from(0x0000_fedc_ba98_7654);
+        let indices = addr.page_table_indices(layout);
+
+        assert_eq!(indices.offset(), Offset::from_items(0x7654));
+        assert_eq!(indices.index(0), Some(0x1a98));
+        assert_eq!(indices.index(1), Some(0x16e5));
+        assert_eq!(indices.index(2), Some(0x3f));
+        assert_eq!(indices.address(), addr);
+
+        let ttbr1 = layout.with_sign_extension(true);
+        let indices = addr.page_table_indices(ttbr1);
+        assert_eq!(indices.address().raw(), 0xffff_fedc_ba98_7654);
+    }
+
+    #[test]
+    fn riscv() {
+        let layout = PageTableLayout::RISCV_SV39;
+        let indices = PageTableIndices::new(layout, &[1, 2, 0x100], 0x10).unwrap();
+
+        assert_eq!(indices.address().raw(), 0xffff_ffc0_0040_1010);
+        assert_eq!(
+            PageTableIndices::new(layout, &[1, 2], 0),
+            Err(Error::InvalidSize)
+        );
+        assert_eq!(
+            PageTableIndices::new(layout, &[1, 2, 512], 0),
+            Err(Error::OutOfRange)
+        );
+        assert_eq!(
+            PageTableIndices::new(layout, &[1, 2, 3], 0x1000),
+            Err(Error::OutOfRange)
+        );
+    }
+}
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::mem::{align_of, size_of};
+use core::str::FromStr;
+
+/// Parses an unsigned integer with an optional radix prefix
+///
+/// Accepts `0x`, `0o` and `0b` prefixes and `_` separators between digits.
+fn integer(s: &str) -> Result<u128, Error> {
+    let (radix, digits) = match s.get(..2) {
+        Some("0x") | Some("0X") => (16, &s[2..]),
+        Some("0o") | Some("0O") => (8, &s[2..]),
+        Some("0b") | Some("0B") => (2, &s[2..]),
+        _ => (10, s),
+    };
+
+    let mut value = None;
+    for c in digits.chars() {
+        if c == '_' {
+            continue;
+        }
+
+        let digit = c.to_digit(radix).ok_or(Error::InvalidDigit)?;
+        value = value
+            .unwrap_or(0u128)
+            .checked_mul(radix.into())
+            .and_then(|v| v.checked_add(digit.into()))
+            .map(Some)
+            .ok_or(Error::OutOfRange)?;
+    }
+
+    value.ok_or(Error::Empty)
+}
+
+/// Parses an `Address`
+///
+/// The value may be given in hexadecimal (`0x`), octal (`0o`), binary (`0b`)
+/// or decimal and may contain `_` separators. Parsing fails if the value is
+/// not properly aligned for `U`.
+impl<T: TryFrom<u128>, U> FromStr for Address<T, U> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = integer(s)?;
+        if value % align_of::<U>() as u128 != 0 {
+            return Err(Error::Misaligned {
+                address: value,
+                align: align_of::<U>(),
+            });
+        }
+
+        let value = T::try_from(value).map_err(|_| Error::OutOfRange)?;
+        Ok(unsafe { Self::unchecked(value) })
+    }
+}
+
+/// Parses an `Offset`
+///
+/// The value may be given in hexadecimal (`0x`), octal (`0o`), binary (`0b`)
+/// or decimal and may contain `_` separators. A plain value is a number of
+/// items. It may instead be followed by one of the binary size suffixes `K`,
+/// `M`, `G` or `T`, which make it a size in bytes: the value is multiplied by
+/// the corresponding power of 1024 and converted to items. Parsing fails
+/// with `Error::Inexact` if that size is not a whole number of items.
+impl<T: TryFrom<u128>, U> FromStr for Offset<T, U> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.get(..2).is_some_and(|p| p.eq_ignore_ascii_case("0x"));
+
+        let (digits, shift) = match s.char_indices().last() {
+            Some((i, 'K')) | Some((i, 'k')) => (&s[..i], 10),
+            Some((i, 'M')) | Some((i, 'm')) => (&s[..i], 20),
+            Some((i, 'G')) | Some((i, 'g')) => (&s[..i], 30),
+            Some((i, 'T')) | Some((i, 't')) => (&s[..i], 40),
+            Some((_, c)) if c.is_ascii_alphabetic() && !hex => return Err(Error::InvalidSuffix),
+            _ => (s, 0),
+        };
+
+        let value = match shift {
+            0 => integer(digits)?,
+            _ => {
+                let bytes = integer(digits)?
+                    .checked_mul(1 << shift)
+                    .ok_or(Error::OutOfRange)?;
+
+                // `Offset::from_bytes_exact()` by hand: calling it would reject
+                // a zero-sized `U` at compile time, even without a suffix.
+                let size = size_of::<U>() as u128;
+                match bytes.checked_rem(size) {
+                    Some(0) => bytes / size,
+                    _ => return Err(Error::Inexact),
+                }
+            }
+        };
+
+        let value = T::try_from(value).map_err(|_| Error::OutOfRange)?;
+        Ok(Self::from_items(value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn address() {
+        assert_eq!(
+            "0x7fff_f000".parse(),
+            Ok(Address::from(0x7fff_f000u64).lower::<Page>())
+        );
+        assert_eq!("0o17".parse(), Ok(Address::<usize, ()>::from(15)));
+        assert_eq!(
+            "0b1000".parse(),
+            Ok(unsafe { Address::<u32, u64>::unchecked(8) })
+        );
+        assert_eq!("4_096".parse(), Ok(Address::<usize, ()>::from(4096)));
+
+        assert_eq!(
+            "0x1001".parse::<Address<u64, Page>>(),
+            Err(Error::Misaligned {
+                address: 0x1001,
+                align: Page::SIZE
+            })
+        );
+        assert_eq!("0x".parse::<Address<u64, ()>>(), Err(Error::Empty));
+        assert_eq!("".parse::<Address<u64, ()>>(), Err(Error::Empty));
+        assert_eq!(
+            "0x12g4".parse::<Address<u64, ()>>(),
+            Err(Error::InvalidDigit)
+        );
+        assert_eq!(
+            "0x1_0000".parse::<Address<u16, ()>>(),
+            Err(Error::OutOfRange)
+        );
+        assert_eq!(
+            "0x1_0000_0000_0000_0000_0000_0000_0000_0000".parse::<Address<u128, ()>>(),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn offset() {
+        assert_eq!("4K".parse(), Ok(Offset::<usize, u8>::from_items(4096)));
+        assert_eq!("2m".parse(), Ok(Offset::<usize, u8>::from_items(2 << 20)));
+        assert_eq!("0x1G".parse(), Ok(Offset::<u64, Page>::from_items(1 << 18)));
+        assert_eq!("2M".parse(), Ok(Offset::<usize, Page>::from_items(512)));
+        assert_eq!("0x10".parse(), Ok(Offset::<usize, Page>::from_items(16)));
+        assert_eq!("0x10".parse(), Ok(Offset::<usize, ()>::from_items(16)));
+        assert_eq!("12k".parse(), Ok(Offset::<u64, u32>::from_items(3072)));
+        assert_eq!("0xff".parse(), Ok(Offset::<u64, u8>::from_items(255)));
+        assert_eq!("0XFF".parse(), Ok(Offset::<u64, u8>::from_items(255)));
+        assert_eq!("0b1_1".parse(), Ok(Offset::<u8, u8>::from_items(3)));
+
+        assert_eq!("4Q".parse::<Offset<u64, u8>>(), Err(Error::InvalidSuffix));
+        assert_eq!("K".parse::<Offset<u64, u8>>(), Err(Error::Empty));
+        assert_eq!("1T".parse::<Offset<u32, u8>>(), Err(Error::OutOfRange));
+        assert_eq!("6K".parse::<Offset<u64, Page>>(), Err(Error::Inexact));
+        assert_eq!("1K".parse::<Offset<u64, ()>>(), Err(Error::Inexact));
+    }
+}
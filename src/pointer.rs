@@ -0,0 +1,295 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::cmp::Ordering;
+use core::mem::{align_of, size_of};
+use core::ops::*;
+
+/// An address that carries pointer provenance
+///
+/// This type offers the same alignment guarantee and `Offset` arithmetic as
+/// `Address<usize, U>`, but it stores a pointer instead of an integer. All
+/// operations are implemented with `map_addr()` and friends, so the resulting
+/// pointers remain valid under strict provenance.
+#[repr(transparent)]
+pub struct PtrAddress<U>(*mut U);
+
+impl<U> Clone for PtrAddress<U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<U> Copy for PtrAddress<U> {}
+
+impl<U> core::fmt::Debug for PtrAddress<U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "PtrAddress(0x{:01$x})",
+            self.0.addr(),
+            size_of::<usize>() * 2
+        ))
+    }
+}
+
+impl<U> core::fmt::Pointer for PtrAddress<U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Pointer::fmt(&self.0, f)
+    }
+}
+
+impl<U> PartialEq for PtrAddress<U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.addr() == other.0.addr()
+    }
+}
+
+impl<U> Eq for PtrAddress<U> {}
+
+impl<U> PartialOrd for PtrAddress<U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<U> Ord for PtrAddress<U> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.addr().cmp(&other.0.addr())
+    }
+}
+
+impl<U> PtrAddress<U> {
+    /// Creates a new `PtrAddress` from a pointer
+    ///
+    /// Fails with `Error::Null` if the pointer is null, or with
+    /// `Error::Misaligned` if it is not properly aligned.
+    #[inline]
+    pub fn new(ptr: *mut U) -> Result<Self, Error> {
+        if ptr.is_null() {
+            return Err(Error::Null);
+        }
+
+        match ptr.is_aligned() {
+            true => Ok(Self(ptr)),
+            false => Err(Error::Misaligned {
+                address: ptr.addr() as u128,
+                align: align_of::<U>(),
+            }),
+        }
+    }
+
+    /// Creates a new `PtrAddress` from a pointer without checking
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be properly aligned for `U`.
+    #[inline]
+    pub const unsafe fn unchecked(ptr: *mut U) -> Self {
+        Self(ptr)
+    }
+
+    /// Returns the address without its provenance
+    #[inline]
+    pub fn address(self) -> Address<usize, U> {
+        unsafe { Address::unchecked(self.0.addr()) }
+    }
+
+    /// Returns a raw pointer to its inner type
+    ///
+    /// # Safety
+    /// Behavior is undefined, if the pointer is used and
+    /// points to uninitialized memory.
+    #[inline]
+    pub const fn as_ptr(self) -> *const U {
+        self.0
+    }
+
+    /// Returns a raw pointer to its inner type
+    ///
+    /// # Safety
+    /// Behavior is undefined, if the pointer is used and
+    /// points to uninitialized memory.
+    #[inline]
+    pub const fn as_mut_ptr(self) -> *mut U {
+        self.0
+    }
+
+    /// Replaces the address, keeping the provenance
+    #[inline]
+    pub fn with_address<V>(self, addr: Address<usize, V>) -> PtrAddress<V> {
+        PtrAddress(self.0.cast::<V>().with_addr(addr.raw()))
+    }
+
+    /// Try casting an existing `PtrAddress` into one of a different type
+    ///
+    /// Succeeds only, if they have compatible alignment
+    #[inline]
+    pub fn try_cast<V>(self) -> Result<PtrAddress<V>, Error> {
+        match self.0.cast::<V>().is_aligned() {
+            true => Ok(PtrAddress(self.0.cast())),
+            false => Err(Error::Misaligned {
+                address: self.0.addr() as u128,
+                align: align_of::<V>(),
+            }),
+        }
+    }
+
+    /// Cast an existing `PtrAddress` into one of a different type by aligning up
+    ///
+    /// Like `Address::raise()`, aligning up past the end of the address space
+    /// panics in debug builds and wraps in release builds.
+    #[inline]
+    pub fn raise<V>(self) -> PtrAddress<V> {
+        let align = align_of::<V>();
+        PtrAddress(
+            self.0
+                .cast::<V>()
+                .map_addr(|addr| addr.next_multiple_of(align)),
+        )
+    }
+
+    /// Cast an existing `PtrAddress` into one of a different type by aligning down
+    #[inline]
+    pub fn lower<V>(self) -> PtrAddress<V> {
+        let align = align_of::<V>();
+        PtrAddress(self.0.cast::<V>().map_addr(|addr| addr / align * align))
+    }
+
+    /// Checked addition of an `Offset`
+    ///
+    /// Returns `None` if the resulting address overflows.
+    #[inline]
+    pub fn checked_add(self, rhs: Offset<usize, U>) -> Option<Self> {
+        let addr = self.address().checked_add(rhs)?;
+        Some(self.with_address(addr))
+    }
+
+    /// Checked subtraction of an `Offset`
+    ///
+    /// Returns `None` if the resulting address underflows.
+    #[inline]
+    pub fn checked_sub(self, rhs: Offset<usize, U>) -> Option<Self> {
+        let addr = self.address().checked_sub(rhs)?;
+        Some(self.with_address(addr))
+    }
+}
+
+/// Convert a reference to a `PtrAddress` with the same type
+impl<U> From<&U> for PtrAddress<U> {
+    #[inline]
+    fn from(value: &U) -> Self {
+        Self(value as *const U as *mut U)
+    }
+}
+
+/// Convert a mutable reference to a `PtrAddress` with the same type
+impl<U> From<&mut U> for PtrAddress<U> {
+    #[inline]
+    fn from(value: &mut U) -> Self {
+        Self(value)
+    }
+}
+
+impl<U> From<PtrAddress<U>> for Address<usize, U> {
+    #[inline]
+    fn from(value: PtrAddress<U>) -> Self {
+        value.address()
+    }
+}
+
+/// Overflow behaves as in `Address` arithmetic: it panics in debug builds
+/// and wraps in release builds. Use `checked_add()` to detect it.
+impl<U> Add<Offset<usize, U>> for PtrAddress<U> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Offset<usize, U>) -> Self::Output {
+        self.with_address(self.address() + rhs)
+    }
+}
+
+impl<U> AddAssign<Offset<usize, U>> for PtrAddress<U> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Offset<usize, U>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<U> Sub<PtrAddress<U>> for PtrAddress<U> {
+    type Output = Offset<usize, U>;
+
+    #[inline]
+    fn sub(self, rhs: PtrAddress<U>) -> Self::Output {
+        self.address() - rhs.address()
+    }
+}
+
+/// Underflow behaves as in `Address` arithmetic: it panics in debug builds
+/// and wraps in release builds. Use `checked_sub()` to detect it.
+impl<U> Sub<Offset<usize, U>> for PtrAddress<U> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Offset<usize, U>) -> Self::Output {
+        self.with_address(self.address() - rhs)
+    }
+}
+
+impl<U> SubAssign<Offset<usize, U>> for PtrAddress<U> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Offset<usize, U>) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut buf = [0u64; 4];
+        let base = PtrAddress::from(&mut buf[0]);
+
+        let third = base + Offset::from_items(2);
+        assert_eq!(third - base, Offset::from_items(2));
+        unsafe { third.as_mut_ptr().write(9) };
+        assert_eq!(buf[2], 9);
+
+        let bytes = third.lower::<u8>() + Offset::from_items(3);
+        assert!(bytes.try_cast::<u64>().is_err());
+        assert_eq!(bytes.lower::<u64>(), third);
+        assert_eq!(bytes.raise::<u64>(), third + Offset::from_items(1));
+    }
+
+    #[test]
+    fn provenance() {
+        let buf = [1u32, 2, 3, 4];
+        let base = PtrAddress::from(&buf[0]);
+
+        let addr = base.address() + Offset::from_items(3);
+        let last = base.with_address(addr);
+        assert_eq!(unsafe { *last.as_ptr() }, 4);
+        assert!(base.checked_sub(Offset::from_items(usize::MAX)).is_none());
+    }
+
+    #[test]
+    fn new() {
+        let mut buf = [0u32; 2];
+        let ptr = buf.as_mut_ptr();
+        assert_eq!(PtrAddress::new(ptr), Ok(PtrAddress::from(&mut buf[0])));
+
+        let byte = ptr.cast::<u8>().wrapping_add(1).cast::<u32>();
+        assert_eq!(
+            PtrAddress::new(byte),
+            Err(Error::Misaligned {
+                address: byte.addr() as u128,
+                align: 4
+            })
+        );
+        assert_eq!(
+            PtrAddress::new(core::ptr::null_mut::<u32>()),
+            Err(Error::Null)
+        );
+    }
+}
@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::mem::align_of;
+use core::ops::*;
+
+impl<T, U> Address<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Add<T, Output = T>,
+{
+    /// Projects the address to a field at `OFFSET` bytes
+    ///
+    /// The function pointer is never called; it only names the field type.
+    /// The alignment of the result is checked at compile time. Use the
+    /// `project!` macro instead of calling this method directly.
+    ///
+    /// # Safety
+    ///
+    /// `OFFSET` must be the offset of the field selected by `field`.
+    #[doc(hidden)]
+    #[inline]
+    pub unsafe fn project_unchecked<F, const OFFSET: usize>(
+        self,
+        field: fn(*const U) -> *const F,
+    ) -> Address<T, F> {
+        let _ = field;
+        const {
+            assert!(
+                align_of::<U>() % align_of::<F>() == 0 && OFFSET % align_of::<F>() == 0,
+                "projected field is not aligned"
+            );
+        }
+
+        let offset: Offset<T, ()> = Offset::from_items(OFFSET).into();
+        Address::unchecked(self.raw() + offset.items())
+    }
+}
+
+/// Projects an `Address` of a struct to an `Address` of one of its fields
+///
+/// The macro takes an `Address<T, S>`, the struct type `S` and a path of
+/// field names, and returns an `Address<T, F>` where `F` is the type of the
+/// field. Fields which may be misaligned, such as those of `repr(packed)`
+/// structs, are rejected at compile time.
+///
+/// ```
+/// use primordial::{project, Address};
+///
+/// #[repr(C)]
+/// struct Gpr {
+///     rax: u64,
+///     rip: u64,
+/// }
+///
+/// #[repr(C)]
+/// struct Ssa {
+///     xsave: [u8; 512],
+///     gpr: Gpr,
+/// }
+///
+/// let ssa = Address::<usize, Ssa>::new(0x1000);
+/// let rip: Address<usize, u64> = project!(ssa, Ssa, gpr.rip);
+/// assert_eq!(rip.raw(), 0x1000 + 512 + 8);
+/// ```
+///
+/// ```compile_fail
+/// use primordial::{project, Address};
+///
+/// #[repr(C, packed)]
+/// struct Packed {
+///     tag: u8,
+///     value: u32,
+/// }
+///
+/// let packed = Address::<usize, Packed>::new(0x1000);
+/// let value = project!(packed, Packed, value);
+/// ```
+#[macro_export]
+macro_rules! project {
+    ($addr:expr, $ty:ty, $($field:tt).+) => {{
+        let addr: $crate::Address<_, $ty> = $addr;
+        unsafe {
+            addr.project_unchecked::<_, { ::core::mem::offset_of!($ty, $($field).+) }>(
+                |base: *const $ty| &raw const (*base).$($field).+,
+            )
+        }
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[derive(Copy, Clone)]
+    #[repr(C)]
+    struct Header {
+        version: u16,
+        flags: u16,
+        size: u32,
+    }
+
+    #[derive(Copy, Clone)]
+    #[repr(C, align(4096))]
+    struct Ghcb {
+        save: [u64; 16],
+        header: Header,
+        buffer: [u8; 64],
+    }
+
+    #[test]
+    fn project() {
+        let ghcb = Address::from(0x7000u64).lower::<Ghcb>();
+        let size: Address<u64, u32> = project!(ghcb, Ghcb, header.size);
+        assert_eq!(size.raw(), 0x7000 + 128 + 4);
+
+        let ghcb = Address::<usize, Ghcb>::new(0x7000);
+        let buffer = project!(ghcb, Ghcb, buffer);
+        let header = project!(ghcb, Ghcb, header);
+        assert_eq!(buffer.raw(), 0x7000 + 136);
+        assert_eq!(project!(header, Header, flags).raw(), 0x7000 + 130);
+        assert_eq!(project!(ghcb, Ghcb, header.version).raw(), header.raw());
+    }
+}
@@ -0,0 +1,416 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+use core::mem::{align_of, size_of};
+use core::ops::*;
+
+/// A half-open range of addresses
+///
+/// A region starts at an `Address<T, U>` and ends just before another
+/// properly aligned address. Both bounds are therefore always aligned for
+/// the type `U`.
+///
+/// Regions are never empty. Operations that could produce an empty region
+/// return `None` instead. Internally, a region stores its last byte rather
+/// than its end so that regions ending at the very top of the address space
+/// can be represented. For such regions, `end()` returns `None`.
+pub struct Region<T, U> {
+    start: T,
+    last: T,
+    unit: PhantomData<U>,
+}
+
+impl<T: Copy, U> Clone for Region<T, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Copy, U> Copy for Region<T, U> {}
+
+impl<T: core::fmt::LowerHex + Copy, U> core::fmt::Debug for Region<T, U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "Region(0x{:02$x}..=0x{:02$x})",
+            self.start,
+            self.last,
+            size_of::<T>() * 2
+        ))
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for Region<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start && self.last == other.last
+    }
+}
+
+impl<T: Eq, U> Eq for Region<T, U> {}
+
+impl<T: PartialOrd, U> PartialOrd for Region<T, U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.start.partial_cmp(&other.start) {
+            Some(Ordering::Equal) => self.last.partial_cmp(&other.last),
+            ordering => ordering,
+        }
+    }
+}
+
+impl<T: Ord, U> Ord for Region<T, U> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start
+            .cmp(&other.start)
+            .then_with(|| self.last.cmp(&other.last))
+    }
+}
+
+impl<T: Copy + Zero, U> Region<T, U> {
+    /// Returns a region used only to fill unused storage slots
+    #[inline]
+    pub(crate) fn placeholder() -> Self {
+        Self {
+            start: T::ZERO,
+            last: T::ZERO,
+            unit: PhantomData,
+        }
+    }
+}
+
+impl<T: Copy + Ord, U> Region<T, U> {
+    /// Creates a region from its first address and the address of its last
+    /// byte
+    ///
+    /// Returns `None` if `last` is below `start`.
+    #[inline]
+    pub(crate) fn inclusive(start: Address<T, U>, last: Address<T, ()>) -> Option<Self> {
+        let (start, last) = (start.raw(), last.raw());
+        match last >= start {
+            true => Some(Self {
+                start,
+                last,
+                unit: PhantomData,
+            }),
+            false => None,
+        }
+    }
+}
+
+#[cfg(test)]
+impl<T: Copy + Ord + One + Sub<T, Output = T>> Region<T, u8> {
+    /// Creates a byte region covering `start..end` in tests
+    pub(crate) fn bytes(start: T, end: T) -> Self {
+        assert!(start < end, "empty region");
+        Self {
+            start,
+            last: end - T::ONE,
+            unit: PhantomData,
+        }
+    }
+}
+
+impl<T: Copy, U> Region<T, U> {
+    /// Returns the first address of the region
+    #[inline]
+    pub fn start(&self) -> Address<T, U> {
+        unsafe { Address::unchecked(self.start) }
+    }
+
+    /// Returns the address of the last byte of the region
+    #[inline]
+    pub fn last(&self) -> Address<T, ()> {
+        Address::from(self.last)
+    }
+}
+
+impl<T, U> Region<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + Ord + Zero + One + Checked + Wrapping,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+{
+    #[inline]
+    fn align<V>() -> T {
+        Offset::from_items(align_of::<V>()).into().items()
+    }
+
+    /// Creates a region from raw bounds, where an `end` of `None` is the top
+    /// of the address space. The bounds must already be aligned for `U`.
+    #[inline]
+    fn from_bounds(start: T, end: Option<T>) -> Option<Self> {
+        let last = match end {
+            Some(end) if end <= start => return None,
+            Some(end) => end - T::ONE,
+            None => T::ZERO.wrapping_sub(T::ONE),
+        };
+
+        Some(Self {
+            start,
+            last,
+            unit: PhantomData,
+        })
+    }
+
+    /// Returns the exclusive end of the region, or `None` at the top
+    #[inline]
+    fn end_raw(&self) -> Option<T> {
+        self.last.checked_add(T::ONE)
+    }
+
+    /// Creates a region covering `start..end`
+    ///
+    /// Returns `None` if the range is empty.
+    #[inline]
+    pub fn new(range: Range<Address<T, U>>) -> Option<Self> {
+        Self::from_bounds(range.start.raw(), Some(range.end.raw()))
+    }
+
+    /// Creates a region of `len` items beginning at `start`
+    ///
+    /// The region may end exactly at the top of the address space. Returns
+    /// `None` if `len` is zero or the region would extend beyond the top of
+    /// the address space.
+    #[inline]
+    pub fn from_offset(start: Address<T, U>, len: Offset<T, U>) -> Option<Self> {
+        let bytes = len.checked_bytes()?;
+        if bytes == T::ZERO {
+            return None;
+        }
+
+        let start = start.raw();
+        let last = start.checked_add(bytes - T::ONE)?;
+        Some(Self {
+            start,
+            last,
+            unit: PhantomData,
+        })
+    }
+
+    /// Returns the exclusive end of the region
+    ///
+    /// Returns `None` if the region ends at the top of the address space.
+    #[inline]
+    pub fn end(&self) -> Option<Address<T, U>> {
+        self.end_raw().map(|end| unsafe { Address::unchecked(end) })
+    }
+
+    /// Returns the number of items in the region
+    ///
+    /// Returns `None` if the count does not fit in `T`, which can only happen
+    /// when the region spans the whole address space.
+    #[inline]
+    pub fn count(&self) -> Option<Offset<T, U>> {
+        let size: T = Offset::from_items(size_of::<U>()).into().items();
+        let items = (self.last - self.start) / size;
+        items.checked_add(T::ONE).map(Offset::from_items)
+    }
+
+    /// Returns whether the region contains the address
+    #[inline]
+    pub fn contains<V>(&self, addr: Address<T, V>) -> bool {
+        let addr = addr.raw();
+        self.start <= addr && addr <= self.last
+    }
+
+    /// Returns whether the two regions share at least one byte
+    #[inline]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.last && other.start <= self.last
+    }
+
+    /// Returns the region covered by both regions, if any
+    #[inline]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        if !self.overlaps(other) {
+            return None;
+        }
+
+        Some(Self {
+            start: core::cmp::max(self.start, other.start),
+            last: core::cmp::min(self.last, other.last),
+            unit: PhantomData,
+        })
+    }
+
+    /// Splits the region into the parts below and at or above `at`
+    ///
+    /// If `at` lies outside the region, one of the parts is `None`.
+    #[inline]
+    pub fn split_at(&self, at: Address<T, U>) -> (Option<Self>, Option<Self>) {
+        let at = at.raw();
+
+        if at <= self.start {
+            return (None, Some(*self));
+        }
+
+        if at > self.last {
+            return (Some(*self), None);
+        }
+
+        let below = Self {
+            start: self.start,
+            last: at - T::ONE,
+            unit: PhantomData,
+        };
+
+        let above = Self {
+            start: at,
+            last: self.last,
+            unit: PhantomData,
+        };
+
+        (Some(below), Some(above))
+    }
+
+    /// Removes `other` from the region
+    ///
+    /// Returns the parts of the region below and above `other`, either of
+    /// which may be `None`.
+    #[inline]
+    pub fn subtract(&self, other: &Self) -> (Option<Self>, Option<Self>) {
+        if !self.overlaps(other) {
+            return match self.start < other.start {
+                true => (Some(*self), None),
+                false => (None, Some(*self)),
+            };
+        }
+
+        let below = Self::from_bounds(self.start, Some(other.start));
+        let above = match other.end_raw() {
+            Some(end) => Self::from_bounds(end, self.end_raw()),
+            None => None,
+        };
+
+        (below, above)
+    }
+
+    /// Returns the union of the two regions
+    ///
+    /// Returns `None` if the regions neither overlap nor touch.
+    #[inline]
+    pub fn merge(&self, other: &Self) -> Option<Self> {
+        let (lo, hi) = match self.start <= other.start {
+            true => (self, other),
+            false => (other, self),
+        };
+
+        match lo.end_raw() {
+            Some(end) if end < hi.start => None,
+            _ => Some(Self {
+                start: lo.start,
+                last: core::cmp::max(lo.last, hi.last),
+                unit: PhantomData,
+            }),
+        }
+    }
+
+    /// Grows the region to the smallest region aligned for `V` containing it
+    #[inline]
+    pub fn raise<V>(&self) -> Region<T, V> {
+        let align = Self::align::<V>();
+
+        Region {
+            start: self.start / align * align,
+            last: self.last / align * align + (align - T::ONE),
+            unit: PhantomData,
+        }
+    }
+
+    /// Shrinks the region to the largest region aligned for `V` inside it
+    ///
+    /// Returns `None` if no such region exists.
+    #[inline]
+    pub fn lower<V>(&self) -> Option<Region<T, V>> {
+        let align = Self::align::<V>();
+
+        let start = self.start.checked_add(align - T::ONE)? / align * align;
+        let end = self.end_raw().map(|end| end / align * align);
+        Region::from_bounds(start, end)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        assert!(Region::<usize, u8>::new(Address::NULL..Address::NULL).is_none());
+        assert_eq!(
+            Region::bytes(2usize, 5).count(),
+            Some(Offset::from_items(3))
+        );
+        assert_eq!(
+            Region::bytes(2usize, 5).end(),
+            Some(Address::from(5).lower())
+        );
+    }
+
+    #[test]
+    fn top() {
+        let start = Address::from(usize::MAX - Page::SIZE + 1).lower::<Page>();
+        let region = Region::from_offset(start, Offset::from_items(1)).unwrap();
+
+        assert_eq!(region.end(), None);
+        assert_eq!(region.last().raw(), usize::MAX);
+        assert_eq!(region.count(), Some(Offset::from_items(1)));
+        assert!(region.contains(Address::from(usize::MAX)));
+        assert!(Region::from_offset(start, Offset::from_items(2)).is_none());
+
+        let full = Region::new(Address::NULL..start)
+            .unwrap()
+            .merge(&region)
+            .unwrap();
+        assert_eq!(full.start(), Address::NULL);
+        assert_eq!(full.end(), None);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = Region::bytes(0x10usize, 0x30);
+        let b = Region::bytes(0x20usize, 0x40);
+        let c = Region::bytes(0x40usize, 0x50);
+
+        assert!(a.overlaps(&b));
+        assert!(!b.overlaps(&c));
+        assert_eq!(a.intersection(&b), Some(Region::bytes(0x20, 0x30)));
+        assert_eq!(b.intersection(&c), None);
+        assert_eq!(a.merge(&c), None);
+        assert_eq!(b.merge(&c), Some(Region::bytes(0x20, 0x50)));
+        assert_eq!(a.subtract(&b), (Some(Region::bytes(0x10, 0x20)), None));
+        assert_eq!(
+            Region::bytes(0x10usize, 0x50).subtract(&b),
+            (
+                Some(Region::bytes(0x10, 0x20)),
+                Some(Region::bytes(0x40, 0x50))
+            )
+        );
+        assert_eq!(c.subtract(&a), (None, Some(c)));
+        assert_eq!(
+            a.split_at(Address::from(0x18).lower()),
+            (
+                Some(Region::bytes(0x10, 0x18)),
+                Some(Region::bytes(0x18, 0x30))
+            )
+        );
+    }
+
+    #[test]
+    fn align() {
+        let r = Region::bytes(0x1234usize, 0x5678);
+
+        let raised = r.raise::<Page>();
+        assert_eq!(raised.start().raw(), 0x1000);
+        assert_eq!(raised.end().unwrap().raw(), 0x6000);
+
+        let lowered = r.lower::<Page>().unwrap();
+        assert_eq!(lowered.start().raw(), 0x2000);
+        assert_eq!(lowered.end().unwrap().raw(), 0x5000);
+
+        assert!(Region::bytes(0x1001usize, 0x1fff).lower::<Page>().is_none());
+    }
+}
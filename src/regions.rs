@@ -0,0 +1,551 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::ops::*;
+
+/// Storage shared by the region set implementations
+trait Store<T, U> {
+    fn slice(&self) -> &[Region<T, U>];
+    fn slice_mut(&mut self) -> &mut [Region<T, U>];
+    fn spare(&self) -> usize;
+    fn insert_at(&mut self, index: usize, region: Region<T, U>);
+    fn remove_range(&mut self, range: Range<usize>);
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> Store<T, U> for alloc::vec::Vec<Region<T, U>> {
+    fn slice(&self) -> &[Region<T, U>] {
+        self
+    }
+
+    fn slice_mut(&mut self) -> &mut [Region<T, U>] {
+        self
+    }
+
+    fn spare(&self) -> usize {
+        usize::MAX
+    }
+
+    fn insert_at(&mut self, index: usize, region: Region<T, U>) {
+        self.insert(index, region)
+    }
+
+    fn remove_range(&mut self, range: Range<usize>) {
+        self.drain(range);
+    }
+}
+
+/// Operations shared by the region set implementations
+///
+/// All regions in the store are kept sorted, disjoint and non-adjacent.
+trait Set<T, U>: Store<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + Ord + Zero + One + Checked + Wrapping,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+{
+    /// Returns the index of the first region ending at or after `addr`
+    fn search(&self, addr: Address<T, ()>) -> usize {
+        self.slice()
+            .partition_point(|r| r.end().is_some_and(|end| end.raw() < addr.raw()))
+    }
+
+    fn contains<V>(&self, addr: Address<T, V>) -> bool {
+        let addr = Address::from(addr.raw());
+        let index = self.search(addr);
+
+        match self.slice().get(index) {
+            Some(region) => region.contains(addr),
+            None => false,
+        }
+    }
+
+    fn insert(&mut self, region: Region<T, U>) -> Result<(), Error> {
+        let start = self.search(Address::from(region.start().raw()));
+
+        let mut merged = region;
+        let mut end = start;
+        while let Some(next) = self.slice().get(end).and_then(|r| r.merge(&merged)) {
+            merged = next;
+            end += 1;
+        }
+
+        if start == end {
+            if self.spare() == 0 {
+                return Err(Error::CapacityExceeded);
+            }
+
+            self.insert_at(start, merged);
+        } else {
+            self.slice_mut()[start] = merged;
+            self.remove_range(start + 1..end);
+        }
+
+        Ok(())
+    }
+
+    fn remove(&mut self, region: Region<T, U>) -> Result<(), Error> {
+        let mut start = self.search(Address::from(region.start().raw()));
+
+        // The first candidate may end exactly at `region.start()` and only
+        // touch the removed region.
+        let touching = self
+            .slice()
+            .get(start)
+            .is_some_and(|r| !r.overlaps(&region));
+        if touching {
+            start += 1;
+        }
+
+        let mut end = start;
+        while self.slice().get(end).is_some_and(|r| r.overlaps(&region)) {
+            end += 1;
+        }
+
+        if start == end {
+            return Ok(());
+        }
+
+        let (below, _) = self.slice()[start].subtract(&region);
+        let (_, above) = self.slice()[end - 1].subtract(&region);
+
+        let parts = [below, above];
+        if parts.iter().flatten().count() > end - start && self.spare() == 0 {
+            return Err(Error::CapacityExceeded);
+        }
+
+        let mut keep = start;
+        for part in parts.iter().flatten() {
+            if keep == end {
+                self.insert_at(keep, *part);
+            } else {
+                self.slice_mut()[keep] = *part;
+            }
+
+            keep += 1;
+        }
+
+        if keep < end {
+            self.remove_range(keep..end);
+        }
+
+        Ok(())
+    }
+
+    fn find_gap<V>(&self, within: Region<T, U>, len: Offset<T, V>) -> Option<Region<T, V>> {
+        let items = len.items();
+        let fit = |gap: Region<T, U>| {
+            let aligned = gap.lower::<V>()?;
+            let region = Region::from_offset(aligned.start(), Offset::from_items(items))?;
+
+            match region.last() <= aligned.last() {
+                true => Some(region),
+                false => None,
+            }
+        };
+
+        let mut rest = within;
+        for region in &self.slice()[self.search(Address::from(within.start().raw()))..] {
+            let (below, above) = rest.subtract(region);
+
+            if let Some(found) = below.and_then(&fit) {
+                return Some(found);
+            }
+
+            rest = above?;
+        }
+
+        fit(rest)
+    }
+}
+
+impl<T, U, S: Store<T, U>> Set<T, U> for S
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + Ord + Zero + One + Checked + Wrapping,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+{
+}
+
+/// An ordered set of non-overlapping regions
+///
+/// Regions inserted into the set are merged with any regions they overlap or
+/// touch. Removing a region splits any region it partially covers. Iteration
+/// always yields the regions in ascending address order.
+#[cfg(feature = "alloc")]
+pub struct RegionSet<T, U>(alloc::vec::Vec<Region<T, U>>);
+
+#[cfg(feature = "alloc")]
+impl<T, U> Default for RegionSet<T, U> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: core::fmt::LowerHex + Copy, U> core::fmt::Debug for RegionSet<T, U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.0.iter()).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, U> IntoIterator for &'a RegionSet<T, U> {
+    type Item = &'a Region<T, U>;
+    type IntoIter = core::slice::Iter<'a, Region<T, U>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> RegionSet<T, U> {
+    /// Creates an empty set
+    #[inline]
+    pub const fn new() -> Self {
+        Self(alloc::vec::Vec::new())
+    }
+
+    /// Returns the number of disjoint regions in the set
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the set is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Removes all regions from the set
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Returns the regions in ascending order
+    #[inline]
+    pub fn as_slice(&self) -> &[Region<T, U>] {
+        &self.0
+    }
+
+    /// Iterates over the regions in ascending order
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, Region<T, U>> {
+        self.0.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> RegionSet<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + Ord + Zero + One + Checked + Wrapping,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+{
+    /// Returns whether any region in the set contains the address
+    #[inline]
+    pub fn contains<V>(&self, addr: Address<T, V>) -> bool {
+        Set::contains(&self.0, addr)
+    }
+
+    /// Adds a region to the set, merging it with its neighbors
+    #[inline]
+    pub fn insert(&mut self, region: Region<T, U>) {
+        let _ = Set::insert(&mut self.0, region);
+    }
+
+    /// Removes a region from the set, splitting regions as necessary
+    #[inline]
+    pub fn remove(&mut self, region: Region<T, U>) {
+        let _ = Set::remove(&mut self.0, region);
+    }
+
+    /// Finds the first gap between regions of the set inside `within`
+    ///
+    /// The returned region is aligned for `V` and holds `len` items. Returns
+    /// `None` if no gap is large enough.
+    #[inline]
+    pub fn find_gap<V>(&self, within: Region<T, U>, len: Offset<T, V>) -> Option<Region<T, V>> {
+        Set::find_gap(&self.0, within, len)
+    }
+}
+
+/// Backing storage for `FixedRegionSet`
+struct Array<T, U, const N: usize> {
+    regions: [Region<T, U>; N],
+    len: usize,
+}
+
+impl<T: Copy, U, const N: usize> Store<T, U> for Array<T, U, N> {
+    fn slice(&self) -> &[Region<T, U>] {
+        &self.regions[..self.len]
+    }
+
+    fn slice_mut(&mut self) -> &mut [Region<T, U>] {
+        &mut self.regions[..self.len]
+    }
+
+    fn spare(&self) -> usize {
+        N - self.len
+    }
+
+    fn insert_at(&mut self, index: usize, region: Region<T, U>) {
+        self.regions.copy_within(index..self.len, index + 1);
+        self.regions[index] = region;
+        self.len += 1;
+    }
+
+    fn remove_range(&mut self, range: Range<usize>) {
+        let count = range.end - range.start;
+        self.regions.copy_within(range.end..self.len, range.start);
+        self.len -= count;
+    }
+}
+
+/// An ordered set of non-overlapping regions with a fixed capacity
+///
+/// This type behaves like `RegionSet`, but it stores at most `N` disjoint
+/// regions inline and never allocates. Operations that would need more space
+/// fail with `Error::CapacityExceeded` and leave the set unchanged.
+pub struct FixedRegionSet<T, U, const N: usize>(Array<T, U, N>);
+
+impl<T: Copy + Zero, U, const N: usize> Default for FixedRegionSet<T, U, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: core::fmt::LowerHex + Copy, U, const N: usize> core::fmt::Debug
+    for FixedRegionSet<T, U, N>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.0.slice().iter()).finish()
+    }
+}
+
+impl<'a, T: Copy, U, const N: usize> IntoIterator for &'a FixedRegionSet<T, U, N> {
+    type Item = &'a Region<T, U>;
+    type IntoIter = core::slice::Iter<'a, Region<T, U>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.slice().iter()
+    }
+}
+
+impl<T: Copy + Zero, U, const N: usize> FixedRegionSet<T, U, N> {
+    /// Creates an empty set
+    #[inline]
+    pub fn new() -> Self {
+        Self(Array {
+            regions: [Region::placeholder(); N],
+            len: 0,
+        })
+    }
+}
+
+impl<T: Copy, U, const N: usize> FixedRegionSet<T, U, N> {
+    /// Returns the maximum number of disjoint regions the set can hold
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of disjoint regions in the set
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len
+    }
+
+    /// Returns whether the set is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.len == 0
+    }
+
+    /// Removes all regions from the set
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.len = 0
+    }
+
+    /// Returns the regions in ascending order
+    #[inline]
+    pub fn as_slice(&self) -> &[Region<T, U>] {
+        self.0.slice()
+    }
+
+    /// Iterates over the regions in ascending order
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, Region<T, U>> {
+        self.0.slice().iter()
+    }
+}
+
+impl<T, U, const N: usize> FixedRegionSet<T, U, N>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + Ord + Zero + One + Checked + Wrapping,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+{
+    /// Returns whether any region in the set contains the address
+    #[inline]
+    pub fn contains<V>(&self, addr: Address<T, V>) -> bool {
+        self.0.contains(addr)
+    }
+
+    /// Adds a region to the set, merging it with its neighbors
+    ///
+    /// Fails if the region does not touch an existing region and the set
+    /// is full.
+    #[inline]
+    pub fn insert(&mut self, region: Region<T, U>) -> Result<(), Error> {
+        self.0.insert(region)
+    }
+
+    /// Removes a region from the set, splitting regions as necessary
+    ///
+    /// Fails if the region splits an existing region in two and the set
+    /// is full.
+    #[inline]
+    pub fn remove(&mut self, region: Region<T, U>) -> Result<(), Error> {
+        self.0.remove(region)
+    }
+
+    /// Finds the first gap between regions of the set inside `within`
+    ///
+    /// The returned region is aligned for `V` and holds `len` items. Returns
+    /// `None` if no gap is large enough.
+    #[inline]
+    pub fn find_gap<V>(&self, within: Region<T, U>, len: Offset<T, V>) -> Option<Region<T, V>> {
+        self.0.find_gap(within, len)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn insert() {
+        let mut set = FixedRegionSet::<usize, u8, 4>::new();
+        set.insert(Region::bytes(0x10, 0x20)).unwrap();
+        set.insert(Region::bytes(0x40, 0x50)).unwrap();
+        set.insert(Region::bytes(0x30, 0x38)).unwrap();
+        assert_eq!(set.len(), 3);
+
+        set.insert(Region::bytes(0x20, 0x30)).unwrap();
+        assert_eq!(
+            set.as_slice(),
+            &[Region::bytes(0x10, 0x38), Region::bytes(0x40, 0x50)]
+        );
+
+        set.insert(Region::bytes(0x00, 0x60)).unwrap();
+        assert_eq!(set.as_slice(), &[Region::bytes(0x00, 0x60)]);
+        assert!(set.contains(Address::from(0x5fusize)));
+        assert!(!set.contains(Address::from(0x60usize)));
+    }
+
+    #[test]
+    fn remove() {
+        let mut set = FixedRegionSet::<usize, u8, 2>::new();
+        set.insert(Region::bytes(0x10, 0x50)).unwrap();
+        set.remove(Region::bytes(0x20, 0x30)).unwrap();
+        assert_eq!(
+            set.as_slice(),
+            &[Region::bytes(0x10, 0x20), Region::bytes(0x30, 0x50)]
+        );
+
+        assert_eq!(
+            set.remove(Region::bytes(0x38, 0x40)),
+            Err(Error::CapacityExceeded)
+        );
+        assert_eq!(set.len(), 2);
+
+        set.remove(Region::bytes(0x18, 0x38)).unwrap();
+        assert_eq!(
+            set.as_slice(),
+            &[Region::bytes(0x10, 0x18), Region::bytes(0x38, 0x50)]
+        );
+
+        set.remove(Region::bytes(0x00, 0x60)).unwrap();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn remove_after_touching() {
+        let mut set = FixedRegionSet::<usize, u8, 2>::new();
+        set.insert(Region::bytes(0x10, 0x20)).unwrap();
+        set.insert(Region::bytes(0x30, 0x50)).unwrap();
+
+        set.remove(Region::bytes(0x20, 0x40)).unwrap();
+        assert_eq!(
+            set.as_slice(),
+            &[Region::bytes(0x10, 0x20), Region::bytes(0x40, 0x50)]
+        );
+    }
+
+    #[test]
+    fn capacity() {
+        let mut set = FixedRegionSet::<usize, u8, 1>::new();
+        set.insert(Region::bytes(0x10, 0x20)).unwrap();
+        assert_eq!(
+            set.insert(Region::bytes(0x30, 0x40)),
+            Err(Error::CapacityExceeded)
+        );
+        set.insert(Region::bytes(0x20, 0x30)).unwrap();
+        assert_eq!(set.as_slice(), &[Region::bytes(0x10, 0x30)]);
+    }
+
+    #[test]
+    fn find_gap() {
+        let mut set = FixedRegionSet::<usize, u8, 4>::new();
+        set.insert(Region::bytes(0x0000, 0x1800)).unwrap();
+        set.insert(Region::bytes(0x2800, 0x3000)).unwrap();
+
+        let within = Region::bytes(0x0000, 0x8000);
+        let gap = set.find_gap::<Page>(within, Offset::from_items(1)).unwrap();
+        assert_eq!(gap.start().raw(), 0x3000);
+
+        let gap = set.find_gap::<u64>(within, Offset::from_items(4)).unwrap();
+        assert_eq!(gap.start().raw(), 0x1800);
+
+        assert!(set
+            .find_gap::<Page>(within, Offset::from_items(6))
+            .is_none());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn alloc() {
+        let mut set = RegionSet::<usize, u8>::new();
+        for i in 0..16 {
+            set.insert(Region::bytes(i * 0x20, i * 0x20 + 0x10));
+        }
+        assert_eq!(set.len(), 16);
+
+        set.insert(Region::bytes(0x08, 0x1f8));
+        assert_eq!(set.as_slice(), &[Region::bytes(0x00, 0x1f8)]);
+
+        set.remove(Region::bytes(0x100, 0x110));
+        assert_eq!(set.iter().count(), 2);
+    }
+}
@@ -267,7 +267,7 @@ implptr! {
 /// underlying types so long as the conversion does not truncate. For example,
 /// `Register<u64>` can be converted to `Register<usize>` on 64-bit systems.
 /// Likewise, `Register<usize>` can be converted to and from a pointer.
-#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct Register<T>(T);
 
@@ -356,6 +356,10 @@ mod tests {
     }
 
     #[test]
+    #[allow(
+        clippy::unnecessary_fallible_conversions,
+        clippy::legacy_numeric_constants
+    )]
     fn signed_from_register_usize() {
         let r = Register::<isize>::from(-1isize);
         let u: Register<usize> = r.try_into().unwrap();
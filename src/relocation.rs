@@ -0,0 +1,415 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::mem::{align_of, size_of};
+use core::ops::*;
+
+/// A move of a range of addresses from an old base to a new base
+///
+/// An address `x` inside the old range is relocated to `new + (x - old)`.
+/// Addresses outside of the old range are rejected.
+pub struct Relocation<T> {
+    source: Region<T, ()>,
+    target: Region<T, ()>,
+}
+
+impl<T: Copy> Clone for Relocation<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Copy> Copy for Relocation<T> {}
+
+impl<T: core::fmt::LowerHex + Copy> core::fmt::Debug for Relocation<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "Relocation({:?} => {:?})",
+            self.source, self.target
+        ))
+    }
+}
+
+impl<T: PartialEq> PartialEq for Relocation<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source && self.target == other.target
+    }
+}
+
+impl<T: Eq> Eq for Relocation<T> {}
+
+impl<T> Relocation<T>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + Ord + Zero + One + Checked + Wrapping,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+{
+    /// Creates a relocation of `size` bytes from `old` to `new`
+    ///
+    /// Fails with `Error::InvalidSize` if `size` is zero and with
+    /// `Error::Overflow` if either range extends beyond the top of the
+    /// address space.
+    #[inline]
+    pub fn new(
+        old: Address<T, ()>,
+        new: Address<T, ()>,
+        size: Offset<T, ()>,
+    ) -> Result<Self, Error> {
+        if size.items() == T::ZERO {
+            return Err(Error::InvalidSize);
+        }
+
+        let bytes: Offset<T, u8> = Offset::from_items(size.items());
+        let region = |start: Address<T, ()>| Region::from_offset(start.lower(), bytes);
+
+        Ok(Self {
+            source: region(old).ok_or(Error::Overflow)?.raise(),
+            target: region(new).ok_or(Error::Overflow)?.raise(),
+        })
+    }
+
+    /// Returns the range of addresses which are relocated
+    #[inline]
+    pub fn source(&self) -> Region<T, ()> {
+        self.source
+    }
+
+    /// Returns the range the addresses are relocated to
+    #[inline]
+    pub fn target(&self) -> Region<T, ()> {
+        self.target
+    }
+
+    /// Returns the relocation in the opposite direction
+    #[inline]
+    pub fn inverse(&self) -> Self {
+        Self {
+            source: self.target,
+            target: self.source,
+        }
+    }
+
+    /// Returns whether the address lies in the relocated range
+    #[inline]
+    pub fn contains<U>(&self, addr: Address<T, U>) -> bool {
+        self.source.contains(addr)
+    }
+
+    #[inline]
+    fn relocate_raw(&self, value: T) -> Option<T> {
+        match self.source.contains(Address::from(value)) {
+            true => Some(self.target.start().raw() + (value - self.source.start().raw())),
+            false => None,
+        }
+    }
+
+    /// Relocates the address referred to by a relocation entry
+    ///
+    /// Unlike `relocate_raw()`, this also accepts the address one past the
+    /// end of the source range, such as the address of `_end`.
+    #[inline]
+    fn relocate_addend(&self, value: T) -> Option<T> {
+        let last = self.source.last().raw();
+        match value > last && value - last == T::ONE {
+            true => self.target.last().raw().checked_add(T::ONE),
+            false => self.relocate_raw(value),
+        }
+    }
+
+    /// Relocates an address
+    ///
+    /// Fails with `Error::OutOfRange` if the address lies outside of the
+    /// relocated range and with `Error::Misaligned` if the relocated address
+    /// is not properly aligned for `U`.
+    #[inline]
+    pub fn relocate<U>(&self, addr: Address<T, U>) -> Result<Address<T, U>, Error>
+    where
+        Address<T, ()>: Into<Address<u128, ()>>,
+    {
+        let value = self.relocate_raw(addr.raw()).ok_or(Error::OutOfRange)?;
+
+        let align: T = Offset::from_items(align_of::<U>()).into().items();
+        match value / align * align == value {
+            true => Ok(unsafe { Address::unchecked(value) }),
+            false => Err(Error::Misaligned {
+                address: Address::from(value).into().raw(),
+                align: align_of::<U>(),
+            }),
+        }
+    }
+
+    /// Relocates a region
+    ///
+    /// Fails with `Error::OutOfRange` if the region does not lie entirely
+    /// inside of the relocated range and with `Error::Misaligned` if the
+    /// relocated region is not properly aligned for `U`.
+    #[inline]
+    pub fn relocate_region<U>(&self, region: Region<T, U>) -> Result<Region<T, U>, Error>
+    where
+        Address<T, ()>: Into<Address<u128, ()>>,
+    {
+        let last = self
+            .relocate_raw(region.last().raw())
+            .ok_or(Error::OutOfRange)?;
+        let start = self.relocate(region.start())?;
+        Region::inclusive(start, Address::from(last)).ok_or(Error::OutOfRange)
+    }
+}
+
+macro_rules! implrelative {
+    ($($t:ident)+) => {
+        $(
+            impl Relocation<$t> {
+                /// Checks that a word at `location` lies in both ranges and
+                /// in the image, and returns its byte offset in the image
+                #[inline]
+                fn location(&self, image: &[u8], location: $t) -> Result<usize, Error> {
+                    let last = location.checked_add(size_of::<$t>() as $t - 1);
+                    let last = last.ok_or(Error::OutOfRange)?;
+                    if !self.contains(Address::from(location)) || !self.contains(Address::from(last)) {
+                        return Err(Error::OutOfRange);
+                    }
+
+                    let index = location - self.source.start().raw();
+                    let index = usize::try_from(index).map_err(|_| Error::OutOfRange)?;
+                    match image.len().checked_sub(size_of::<$t>()) {
+                        Some(limit) if index <= limit => Ok(index),
+                        _ => Err(Error::OutOfRange),
+                    }
+                }
+
+                /// Checks that no two words to patch overlap
+                ///
+                /// Relocation lists are normally sorted, which is checked in a
+                /// single pass. Other lists fall back to comparing every pair.
+                fn disjoint<I>(locations: I) -> Result<(), Error>
+                where
+                    I: Iterator<Item = $t> + Clone,
+                {
+                    let size = size_of::<$t>() as $t;
+                    let mut pairs = locations.clone().zip(locations.clone().skip(1));
+                    if pairs.all(|(a, b)| b >= a && b - a >= size) {
+                        return Ok(());
+                    }
+
+                    for (i, a) in locations.clone().enumerate() {
+                        if locations.clone().skip(i + 1).any(|b| a.abs_diff(b) < size) {
+                            return Err(Error::Overlap);
+                        }
+                    }
+
+                    Ok(())
+                }
+
+                /// Applies `R_*_RELATIVE` relocations with explicit addends
+                ///
+                /// The image holds the contents of the source range, beginning
+                /// at the old base. Each entry is a pair of the link-time
+                /// address of the word to patch and its addend, which is the
+                /// link-time address it refers to. The addend may also be the
+                /// end of the source range. Each word is set to the relocated
+                /// addend, in little-endian byte order.
+                ///
+                /// All entries are checked before the image is modified. Fails
+                /// with `Error::Overlap` if two entries patch overlapping words.
+                pub fn apply_rela<P: AsMut<[Page]>>(
+                    &self,
+                    image: &mut Pages<P>,
+                    entries: &[($t, $t)],
+                ) -> Result<(), Error> {
+                    let image: &mut [u8] = image.as_mut();
+                    for (location, addend) in entries {
+                        self.location(image, *location)?;
+                        self.relocate_addend(*addend).ok_or(Error::OutOfRange)?;
+                    }
+
+                    Self::disjoint(entries.iter().map(|(location, _)| *location))?;
+
+                    for (location, addend) in entries {
+                        let index = self.location(image, *location)?;
+                        let value = self.relocate_addend(*addend).ok_or(Error::OutOfRange)?;
+                        image[index..][..size_of::<$t>()].copy_from_slice(&value.to_le_bytes());
+                    }
+
+                    Ok(())
+                }
+
+                /// Applies `R_*_RELATIVE` relocations with implicit addends
+                ///
+                /// This is like `apply_rela()`, except that the addend of each
+                /// entry is read from the word being patched.
+                pub fn apply_rel<P: AsMut<[Page]>>(
+                    &self,
+                    image: &mut Pages<P>,
+                    locations: &[$t],
+                ) -> Result<(), Error> {
+                    let image: &mut [u8] = image.as_mut();
+                    let read = |image: &[u8], index: usize| {
+                        let mut word = [0; size_of::<$t>()];
+                        word.copy_from_slice(&image[index..][..size_of::<$t>()]);
+                        $t::from_le_bytes(word)
+                    };
+
+                    for location in locations {
+                        let index = self.location(image, *location)?;
+                        self.relocate_addend(read(image, index)).ok_or(Error::OutOfRange)?;
+                    }
+
+                    Self::disjoint(locations.iter().copied())?;
+
+                    for location in locations {
+                        let index = self.location(image, *location)?;
+                        let value = self.relocate_addend(read(image, index)).ok_or(Error::OutOfRange)?;
+                        image[index..][..size_of::<$t>()].copy_from_slice(&value.to_le_bytes());
+                    }
+
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+implrelative! { u64 usize }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn relocate() {
+        let rel = Relocation::new(
+            Address::from(0x40_0000u64),
+            Address::from(0x7f00_0000_0000u64),
+            Offset::from_items(0x2000),
+        )
+        .unwrap();
+
+        let addr = Address::from(0x40_1008u64).lower::<u64>();
+        assert_eq!(rel.relocate(addr).unwrap().raw(), 0x7f00_0000_1008);
+        assert_eq!(
+            rel.relocate(Address::from(0x40_2000u64)),
+            Err(Error::OutOfRange)
+        );
+        assert_eq!(
+            rel.inverse().relocate(rel.relocate(addr).unwrap()),
+            Ok(addr)
+        );
+
+        let region = Region::new(Address::from(0x40_0010u64)..Address::from(0x40_0020u64)).unwrap();
+        let moved = rel.relocate_region(region).unwrap();
+        assert_eq!(moved.start().raw(), 0x7f00_0000_0010);
+        assert_eq!(moved.last().raw(), 0x7f00_0000_001f);
+
+        let region = Region::new(Address::from(0x40_1000u64)..Address::from(0x40_3000u64)).unwrap();
+        assert_eq!(rel.relocate_region(region), Err(Error::OutOfRange));
+
+        let odd = Relocation::new(
+            Address::from(0u64),
+            Address::from(1u64),
+            Offset::from_items(0x10),
+        )
+        .unwrap();
+        assert_eq!(
+            odd.relocate(Address::from(8u64).lower::<u64>()),
+            Err(Error::Misaligned {
+                address: 9,
+                align: 8
+            })
+        );
+        assert_eq!(
+            Relocation::new(
+                Address::from(0u64),
+                Address::from(u64::MAX),
+                Offset::from_items(2)
+            ),
+            Err(Error::Overflow)
+        );
+        assert_eq!(
+            Relocation::new(
+                Address::from(0u64),
+                Address::from(0u64),
+                Offset::from_items(0)
+            ),
+            Err(Error::InvalidSize)
+        );
+    }
+
+    #[test]
+    fn relative() {
+        let mut image = Pages::new([Page::zeroed(); 2]);
+        let rel = Relocation::new(
+            Address::from(0u64),
+            Address::from(0x5555_0000_0000u64),
+            Offset::from_items(2 * Page::SIZE as u64),
+        )
+        .unwrap();
+
+        let bytes: &mut [u8] = image.as_mut();
+        bytes[0x1010..0x1018].copy_from_slice(&0x1234u64.to_le_bytes());
+
+        rel.apply_rela(&mut image, &[(0x1000, 0x20), (0x1008, 0x1ff8)])
+            .unwrap();
+        rel.apply_rel(&mut image, &[0x1010]).unwrap();
+
+        let bytes: &[u8] = image.as_ref();
+        assert_eq!(bytes[0x1000..0x1008], 0x5555_0000_0020u64.to_le_bytes());
+        assert_eq!(bytes[0x1008..0x1010], 0x5555_0000_1ff8u64.to_le_bytes());
+        assert_eq!(bytes[0x1010..0x1018], 0x5555_0000_1234u64.to_le_bytes());
+
+        let mut before = [0; 8];
+        before.copy_from_slice(&bytes[0x1000..0x1008]);
+        assert_eq!(
+            rel.apply_rela(&mut image, &[(0x0, 0x8), (0x1ffc, 0x8)]),
+            Err(Error::OutOfRange)
+        );
+        assert_eq!(
+            rel.apply_rela(&mut image, &[(0x1000, 0x2001)]),
+            Err(Error::OutOfRange)
+        );
+
+        let bytes: &[u8] = image.as_ref();
+        assert_eq!(bytes[0..8], [0; 8]);
+        assert_eq!(bytes[0x1000..0x1008], before);
+
+        rel.apply_rela(&mut image, &[(0x0, 0x2000)]).unwrap();
+        let bytes: &[u8] = image.as_ref();
+        assert_eq!(bytes[0..8], 0x5555_0000_2000u64.to_le_bytes());
+    }
+
+    #[test]
+    fn overlap() {
+        let mut image = Pages::new([Page::zeroed(); 1]);
+        let rel = Relocation::new(
+            Address::from(0u64),
+            Address::from(0x1_0000u64),
+            Offset::from_items(Page::SIZE as u64),
+        )
+        .unwrap();
+
+        let bytes: &mut [u8] = image.as_mut();
+        bytes[0x10..0x18].copy_from_slice(&0x100u64.to_le_bytes());
+        bytes[0x20..0x28].copy_from_slice(&0x200u64.to_le_bytes());
+
+        assert_eq!(
+            rel.apply_rel(&mut image, &[0x20, 0x10, 0x20]),
+            Err(Error::Overlap)
+        );
+        assert_eq!(
+            rel.apply_rela(&mut image, &[(0x10, 0x8), (0x14, 0x8)]),
+            Err(Error::Overlap)
+        );
+
+        let bytes: &[u8] = image.as_ref();
+        assert_eq!(bytes[0x10..0x18], 0x100u64.to_le_bytes());
+        assert_eq!(bytes[0x20..0x28], 0x200u64.to_le_bytes());
+
+        rel.apply_rel(&mut image, &[0x20, 0x10]).unwrap();
+        let bytes: &[u8] = image.as_ref();
+        assert_eq!(bytes[0x10..0x18], 0x1_0100u64.to_le_bytes());
+        assert_eq!(bytes[0x20..0x28], 0x1_0200u64.to_le_bytes());
+    }
+}
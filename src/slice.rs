@@ -0,0 +1,324 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::ops::*;
+
+/// The address of a run of `len` items of type `U`
+///
+/// This type pairs an `Address<T, U>` with a length, describing a buffer in
+/// the same way a pointer and a count do. Like `Address`, it guarantees that
+/// the base is properly aligned for `U`. It also guarantees that the end of
+/// the run does not overflow `T`.
+pub struct SliceAddress<T, U> {
+    addr: T,
+    len: T,
+    unit: PhantomData<U>,
+}
+
+impl<T: Copy, U> Clone for SliceAddress<T, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Copy, U> Copy for SliceAddress<T, U> {}
+
+impl<T: core::fmt::LowerHex + core::fmt::Display, U> core::fmt::Debug for SliceAddress<T, U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "SliceAddress(0x{:02$x}; {})",
+            self.addr,
+            self.len,
+            size_of::<T>() * 2
+        ))
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for SliceAddress<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.addr == other.addr && self.len == other.len
+    }
+}
+
+impl<T: Eq, U> Eq for SliceAddress<T, U> {}
+
+impl<T: Copy, U> SliceAddress<T, U> {
+    /// Returns the address of the first item
+    #[inline]
+    pub fn address(&self) -> Address<T, U> {
+        unsafe { Address::unchecked(self.addr) }
+    }
+
+    /// Returns the number of items
+    #[inline]
+    pub fn len(&self) -> Offset<T, U> {
+        Offset::from_items(self.len)
+    }
+}
+
+impl<T, U> SliceAddress<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + Ord + Zero + One + Checked + Wrapping,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+{
+    /// Creates a new slice address
+    ///
+    /// Fails with `Error::Overflow` if the end of the run overflows `T`.
+    #[inline]
+    pub fn new(addr: Address<T, U>, len: Offset<T, U>) -> Result<Self, Error> {
+        let addr = addr.raw();
+        let len = len.items();
+        Offset::<T, U>::from_items(len)
+            .checked_bytes()
+            .and_then(|bytes| addr.checked_add(bytes))
+            .ok_or(Error::Overflow)?;
+
+        Ok(Self {
+            addr,
+            len,
+            unit: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn bytes(items: T) -> T {
+        Offset::<T, U>::from_items(items).bytes()
+    }
+
+    /// Returns whether the run holds no items
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == T::ZERO
+    }
+
+    /// Returns the bytes covered by the run
+    #[inline]
+    pub fn byte_range(&self) -> Range<Address<T, u8>> {
+        let end = self.addr + Self::bytes(self.len);
+        Address::from(self.addr).lower()..Address::from(end).lower()
+    }
+
+    /// Returns the bytes covered by the run as a `Region`
+    ///
+    /// Returns `None` if the run is empty.
+    #[inline]
+    pub fn region(&self) -> Option<Region<T, U>> {
+        Region::from_offset(self.address(), self.len())
+    }
+
+    /// Divides the run into two at the index `mid`
+    ///
+    /// Returns `None` if `mid` is greater than the length.
+    #[inline]
+    pub fn split_at(&self, mid: Offset<T, U>) -> Option<(Self, Self)> {
+        let mid = mid.items();
+        if mid > self.len {
+            return None;
+        }
+
+        let head = Self {
+            addr: self.addr,
+            len: mid,
+            unit: PhantomData,
+        };
+
+        let tail = Self {
+            addr: self.addr + Self::bytes(mid),
+            len: self.len - mid,
+            unit: PhantomData,
+        };
+
+        Some((head, tail))
+    }
+
+    /// Returns the items in `range` as a new run
+    ///
+    /// Returns `None` if the range is out of bounds or reversed.
+    #[inline]
+    pub fn subslice(&self, range: Range<Offset<T, U>>) -> Option<Self> {
+        let start = range.start.items();
+        let end = range.end.items();
+        if start > end || end > self.len {
+            return None;
+        }
+
+        Some(Self {
+            addr: self.addr + Self::bytes(start),
+            len: end - start,
+            unit: PhantomData,
+        })
+    }
+}
+
+impl<T, U> SliceAddress<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + Ord + Zero + One + Checked + Wrapping,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+    Address<T, U>: Into<Address<usize, U>>,
+    Offset<T, U>: Into<Offset<usize, U>>,
+{
+    /// Checks that the run can be turned into a slice
+    #[inline]
+    fn parts(&self) -> Option<(usize, usize)> {
+        let addr = self.address().into().raw();
+        let len = self.len().into().items();
+
+        if len != 0 && (addr == 0 || len.checked_mul(size_of::<U>())? > isize::MAX as usize) {
+            return None;
+        }
+
+        Some((addr, len))
+    }
+
+    /// Converts the run to a slice
+    ///
+    /// An empty run always produces an empty slice. Otherwise, returns `None`
+    /// if the base is null or the run is too large for a slice.
+    ///
+    /// # Safety
+    ///
+    /// The caller MUST ensure that the run points to valid, initialized
+    /// memory that is not mutated for the lifetime `'a`.
+    #[inline]
+    pub unsafe fn as_slice<'a>(&self) -> Option<&'a [U]> {
+        match self.parts()? {
+            (_, 0) => Some(&[]),
+            (addr, len) => Some(core::slice::from_raw_parts(
+                core::ptr::with_exposed_provenance(addr),
+                len,
+            )),
+        }
+    }
+
+    /// Converts the run to a mutable slice
+    ///
+    /// An empty run always produces an empty slice. Otherwise, returns `None`
+    /// if the base is null or the run is too large for a slice.
+    ///
+    /// # Safety
+    ///
+    /// The caller MUST ensure that the run points to valid, initialized
+    /// memory that is not otherwise accessed for the lifetime `'a`.
+    #[inline]
+    pub unsafe fn as_mut_slice<'a>(&self) -> Option<&'a mut [U]> {
+        match self.parts()? {
+            (_, 0) => Some(&mut []),
+            (addr, len) => Some(core::slice::from_raw_parts_mut(
+                core::ptr::with_exposed_provenance_mut(addr),
+                len,
+            )),
+        }
+    }
+}
+
+/// Convert a slice to a `SliceAddress` with the same type
+impl<T, U> From<&[U]> for SliceAddress<T, U>
+where
+    Address<usize, U>: Into<Address<T, U>>,
+    Offset<usize, U>: Into<Offset<T, U>>,
+{
+    #[inline]
+    fn from(value: &[U]) -> Self {
+        let addr: Address<usize, U> = Address::from(value.as_ptr());
+        let len: Offset<usize, U> = Offset::from_items(value.len());
+
+        Self {
+            addr: addr.into().raw(),
+            len: len.into().items(),
+            unit: PhantomData,
+        }
+    }
+}
+
+/// Convert a mutable slice to a `SliceAddress` with the same type
+impl<T, U> From<&mut [U]> for SliceAddress<T, U>
+where
+    Address<usize, U>: Into<Address<T, U>>,
+    Offset<usize, U>: Into<Offset<T, U>>,
+{
+    #[inline]
+    fn from(value: &mut [U]) -> Self {
+        let addr: Address<usize, U> = Address::from(value.as_mut_ptr());
+        let len: Offset<usize, U> = Offset::from_items(value.len());
+
+        Self {
+            addr: addr.into().raw(),
+            len: len.into().items(),
+            unit: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split() {
+        let buf = [1u32, 2, 3, 4, 5];
+        let run = SliceAddress::<usize, u32>::from(&buf[..]);
+
+        let (head, tail) = run.split_at(Offset::from_items(2)).unwrap();
+        assert_eq!(unsafe { head.as_slice() }, Some(&buf[..2]));
+        assert_eq!(unsafe { tail.as_slice() }, Some(&buf[2..]));
+        assert_eq!(tail.address(), run.address() + Offset::from_items(2));
+        assert!(run.split_at(Offset::from_items(6)).is_none());
+
+        let mid = run
+            .subslice(Offset::from_items(1)..Offset::from_items(4))
+            .unwrap();
+        assert_eq!(unsafe { mid.as_slice() }, Some(&buf[1..4]));
+        assert!(run
+            .subslice(Offset::from_items(3)..Offset::from_items(6))
+            .is_none());
+
+        let bytes = mid.byte_range();
+        assert_eq!(bytes.end - bytes.start, Offset::from_items(12));
+        assert_eq!(mid.region().unwrap().start(), mid.address());
+    }
+
+    #[test]
+    fn checked() {
+        let null = SliceAddress::<usize, u64>::new(Address::NULL, Offset::from_items(1)).unwrap();
+        assert_eq!(unsafe { null.as_slice() }, None);
+
+        let empty = SliceAddress::<usize, u64>::new(Address::NULL, Offset::from_items(0)).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(unsafe { empty.as_slice() }, Some(&[][..]));
+        assert!(empty.region().is_none());
+
+        let huge = SliceAddress::<usize, u64>::new(
+            Address::new(8),
+            Offset::from_items(usize::MAX / 8 - 1),
+        );
+        assert!(unsafe { huge.unwrap().as_slice() }.is_none());
+        assert_eq!(
+            SliceAddress::<usize, u64>::new(Address::new(8), Offset::from_items(usize::MAX / 8)),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn mutable() {
+        let mut buf = [0u8; 4];
+        let run = SliceAddress::<u64, u8>::from(&mut buf[..]);
+        let slice = unsafe {
+            run.subslice(Offset::from_items(2)..Offset::from_items(4))
+                .unwrap()
+                .as_mut_slice()
+        };
+
+        slice.unwrap().fill(7);
+        assert_eq!(buf, [0, 0, 7, 7]);
+    }
+}
@@ -0,0 +1,325 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::ops::*;
+
+/// An address space
+///
+/// Types implementing this trait are used as markers to distinguish addresses
+/// in different address spaces. Additional spaces, such as a guest-physical
+/// space, can be defined by implementing this trait on an uninhabited type.
+pub trait AddressSpace {
+    /// A short name for the address space used when formatting
+    const NAME: &'static str;
+}
+
+/// The physical address space
+#[derive(Copy, Clone, Debug)]
+pub enum Physical {}
+
+impl AddressSpace for Physical {
+    const NAME: &'static str = "PhysAddr";
+}
+
+/// The virtual address space
+#[derive(Copy, Clone, Debug)]
+pub enum Virtual {}
+
+impl AddressSpace for Virtual {
+    const NAME: &'static str = "VirtAddr";
+}
+
+/// An address in a specific address space
+///
+/// This type wraps an `Address<T, U>` and tags it with the address space `S`.
+/// Addresses in different spaces cannot be compared or combined, and the only
+/// way to move an address into another space is an explicit `translate()`.
+/// Only addresses in the `Virtual` space can be converted into pointers.
+///
+/// ```compile_fail
+/// use primordial::{Address, PhysAddr, VirtAddr};
+///
+/// let phys = PhysAddr::new(Address::<usize, u8>::new(0x1000));
+/// let virt = VirtAddr::new(Address::<usize, u8>::new(0x1000));
+/// assert!(phys == virt);
+/// ```
+#[repr(transparent)]
+pub struct SpaceAddress<S, T, U>(Address<T, U>, PhantomData<S>);
+
+/// A physical address
+pub type PhysAddr<T, U> = SpaceAddress<Physical, T, U>;
+
+/// A virtual address
+pub type VirtAddr<T, U> = SpaceAddress<Virtual, T, U>;
+
+impl<S, T, U> Clone for SpaceAddress<S, T, U>
+where
+    Address<T, U>: Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<S, T, U> Copy for SpaceAddress<S, T, U> where Address<T, U>: Copy {}
+
+impl<S: AddressSpace, T: core::fmt::LowerHex, U> core::fmt::Debug for SpaceAddress<S, T, U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(S::NAME)?;
+        f.write_fmt(format_args!("(0x{:01$x})", self.0, size_of::<T>() * 2))
+    }
+}
+
+impl<S, T: core::fmt::LowerHex, U> core::fmt::LowerHex for SpaceAddress<S, T, U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl<S, T: core::fmt::UpperHex, U> core::fmt::UpperHex for SpaceAddress<S, T, U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl<S, T: PartialEq, U> PartialEq for SpaceAddress<S, T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<S, T: Eq, U> Eq for SpaceAddress<S, T, U> {}
+
+impl<S, T: PartialOrd, U> PartialOrd for SpaceAddress<S, T, U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<S, T: Ord, U> Ord for SpaceAddress<S, T, U> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<S, T: Zero, U> SpaceAddress<S, T, U> {
+    /// The NULL address
+    pub const NULL: Self = Self(Address::NULL, PhantomData);
+}
+
+impl<S, T, U> SpaceAddress<S, T, U> {
+    /// Places an `Address` into the address space `S`
+    #[inline]
+    pub const fn new(addr: Address<T, U>) -> Self {
+        Self(addr, PhantomData)
+    }
+
+    /// Returns the `Address` without its address space
+    #[inline]
+    pub fn address(self) -> Address<T, U> {
+        self.0
+    }
+
+    /// Converts a `SpaceAddress` to its raw inner type
+    #[inline]
+    pub fn raw(self) -> T {
+        self.0.raw()
+    }
+
+    /// Translates the address into the address space `D`
+    ///
+    /// The mapping between the two spaces is performed by `f`.
+    #[inline]
+    pub fn translate<D, F>(self, f: F) -> SpaceAddress<D, T, U>
+    where
+        F: FnOnce(Address<T, U>) -> Address<T, U>,
+    {
+        SpaceAddress(f(self.0), PhantomData)
+    }
+}
+
+impl<S, T, U> SpaceAddress<S, T, U>
+where
+    Address<T, U>: Into<Address<usize, U>>,
+    Address<T, U>: From<Address<usize, U>>,
+{
+    /// Try casting an existing `SpaceAddress` into one of a different type
+    ///
+    /// Succeeds only, if they have compatible alignment
+    #[inline]
+    pub fn try_cast<V>(self) -> Result<SpaceAddress<S, T, V>, Error> {
+        Ok(SpaceAddress(self.0.try_cast()?, PhantomData))
+    }
+}
+
+impl<S, T, U> SpaceAddress<S, T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+    T: One,
+{
+    /// Cast an existing `SpaceAddress` into one of a different type by aligning up
+    #[inline]
+    pub fn raise<V>(self) -> SpaceAddress<S, T, V> {
+        SpaceAddress(self.0.raise(), PhantomData)
+    }
+
+    /// Cast an existing `SpaceAddress` into one of a different type by aligning down
+    #[inline]
+    pub fn lower<V>(self) -> SpaceAddress<S, T, V> {
+        SpaceAddress(self.0.lower(), PhantomData)
+    }
+}
+
+impl<S, T, U> SpaceAddress<S, T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Checked,
+{
+    /// Checked addition of an `Offset`
+    ///
+    /// Returns `None` if the resulting address overflows.
+    #[inline]
+    pub fn checked_add(self, rhs: Offset<T, U>) -> Option<Self> {
+        Some(Self(self.0.checked_add(rhs)?, PhantomData))
+    }
+
+    /// Checked subtraction of an `Offset`
+    ///
+    /// Returns `None` if the resulting address underflows.
+    #[inline]
+    pub fn checked_sub(self, rhs: Offset<T, U>) -> Option<Self> {
+        Some(Self(self.0.checked_sub(rhs)?, PhantomData))
+    }
+}
+
+impl<T, U> SpaceAddress<Virtual, T, U>
+where
+    Address<T, U>: Into<Address<usize, U>>,
+{
+    /// Returns a raw pointer to its inner type
+    ///
+    /// # Safety
+    /// Behavior is undefined, if the pointer is used and
+    /// is not aligned or points to uninitialized memory.
+    #[inline]
+    pub fn as_ptr(self) -> *const U {
+        self.0.as_ptr()
+    }
+
+    /// Returns a raw pointer to its inner type
+    ///
+    /// # Safety
+    /// Behavior is undefined, if the pointer is used and
+    /// is not aligned or points to uninitialized memory.
+    #[inline]
+    pub fn as_mut_ptr(self) -> *mut U {
+        self.0.as_mut_ptr()
+    }
+}
+
+/// Convert a reference to a virtual address with the same type
+impl<T, U> From<&U> for SpaceAddress<Virtual, T, U>
+where
+    Address<usize, U>: Into<Address<T, U>>,
+{
+    #[inline]
+    fn from(value: &U) -> Self {
+        Self(Address::from(value), PhantomData)
+    }
+}
+
+impl<S, T, U> Add<Offset<T, U>> for SpaceAddress<S, T, U>
+where
+    Address<T, U>: Add<Offset<T, U>, Output = Address<T, U>>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Offset<T, U>) -> Self::Output {
+        Self(self.0 + rhs, PhantomData)
+    }
+}
+
+impl<S, T, U> AddAssign<Offset<T, U>> for SpaceAddress<S, T, U>
+where
+    Address<T, U>: AddAssign<Offset<T, U>>,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: Offset<T, U>) {
+        self.0 += rhs;
+    }
+}
+
+impl<S, T, U> Sub<SpaceAddress<S, T, U>> for SpaceAddress<S, T, U>
+where
+    Address<T, U>: Sub<Address<T, U>, Output = Offset<T, U>>,
+{
+    type Output = Offset<T, U>;
+
+    #[inline]
+    fn sub(self, rhs: SpaceAddress<S, T, U>) -> Self::Output {
+        self.0 - rhs.0
+    }
+}
+
+impl<S, T, U> Sub<Offset<T, U>> for SpaceAddress<S, T, U>
+where
+    Address<T, U>: Sub<Offset<T, U>, Output = Address<T, U>>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Offset<T, U>) -> Self::Output {
+        Self(self.0 - rhs, PhantomData)
+    }
+}
+
+impl<S, T, U> SubAssign<Offset<T, U>> for SpaceAddress<S, T, U>
+where
+    Address<T, U>: SubAssign<Offset<T, U>>,
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: Offset<T, U>) {
+        self.0 -= rhs;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use std::format;
+
+    #[test]
+    fn arithmetic() {
+        let phys = PhysAddr::new(Address::from(0x1234u64)).raise::<Page>();
+        assert_eq!(phys.raw(), 0x2000);
+
+        let next = phys + Offset::from_items(2);
+        assert_eq!(next.raw(), 0x4000);
+        assert_eq!(next - phys, Offset::from_items(2));
+        assert_eq!(
+            format!("{:?}", next.lower::<u8>()),
+            "PhysAddr(0x0000000000004000)"
+        );
+    }
+
+    #[test]
+    fn translate() {
+        let phys = PhysAddr::<usize, u32>::new(Address::new(0x1000));
+        let virt: VirtAddr<_, _> = phys.translate(|addr| addr + Offset::from_items(0x100));
+        assert_eq!(virt.raw(), 0x1400);
+
+        let value = 7u32;
+        let virt = VirtAddr::<usize, u32>::from(&value);
+        assert_eq!(unsafe { *virt.as_ptr() }, 7);
+    }
+}
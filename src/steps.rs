@@ -0,0 +1,340 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::marker::PhantomData;
+use core::ops::*;
+
+/// An iterator over addresses in fixed strides
+///
+/// The iterator yields every address from a start address up to an end
+/// address, advancing by a fixed `Offset` each time. It tracks both ends
+/// inclusively, so it never computes an address beyond the last one it
+/// yields and cannot overflow at the top of the address space.
+pub struct Steps<T, U> {
+    front: T,
+    back: T,
+    step: T,
+    done: bool,
+    unit: PhantomData<U>,
+}
+
+impl<T: Copy, U> Clone for Steps<T, U> {
+    fn clone(&self) -> Self {
+        Self {
+            front: self.front,
+            back: self.back,
+            step: self.step,
+            done: self.done,
+            unit: PhantomData,
+        }
+    }
+}
+
+impl<T: core::fmt::LowerHex, U> core::fmt::Debug for Steps<T, U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "Steps(0x{:x}..=0x{:x}, step: 0x{:x})",
+            self.front, self.back, self.step
+        ))
+    }
+}
+
+impl<T, U> Steps<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + Ord + Zero + One + Checked,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+{
+    #[inline]
+    fn inclusive(front: T, back: T, step: Offset<T, U>) -> Self {
+        let bytes = step.checked_bytes();
+        let done = bytes == Some(T::ZERO) || back < front;
+
+        // A step that overflows `T` is larger than any range, so only the
+        // first address is yielded.
+        let (step, back) = match (done, bytes) {
+            (true, _) => (T::ZERO, back),
+            (false, Some(step)) => (step, front + (back - front) / step * step),
+            (false, None) => (T::ZERO, front),
+        };
+
+        Self {
+            front,
+            back,
+            step,
+            done,
+            unit: PhantomData,
+        }
+    }
+
+    /// Creates an iterator over `range` in strides of `step`
+    ///
+    /// The iterator is empty if the range is empty or `step` is zero bytes.
+    #[inline]
+    pub fn new(range: Range<Address<T, U>>, step: Offset<T, U>) -> Self {
+        let front = range.start.raw();
+        let end = range.end.raw();
+
+        match front < end {
+            true => Self::inclusive(front, end - T::ONE, step),
+            false => Self::inclusive(T::ONE, T::ZERO, step),
+        }
+    }
+}
+
+impl<T, U> Iterator for Steps<T, U>
+where
+    T: Copy + Eq + Zero,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Div<T, Output = T>,
+    usize: TryFrom<T>,
+{
+    type Item = Address<T, U>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let addr = self.front;
+        match addr == self.back {
+            true => self.done = true,
+            false => self.front = addr + self.step,
+        }
+
+        Some(unsafe { Address::unchecked(addr) })
+    }
+
+    /// The bounds are exact unless the number of remaining addresses does
+    /// not fit in a `usize`, e.g. for every byte of the address space.
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match (self.done, self.step == T::ZERO) {
+            (true, _) => Some(0),
+            (false, true) => Some(1),
+            (false, false) => usize::try_from((self.back - self.front) / self.step)
+                .ok()
+                .and_then(|n| n.checked_add(1)),
+        };
+
+        match len {
+            Some(len) => (len, Some(len)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl<T, U> DoubleEndedIterator for Steps<T, U>
+where
+    T: Copy + Eq + Zero,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Div<T, Output = T>,
+    usize: TryFrom<T>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let addr = self.back;
+        match addr == self.front {
+            true => self.done = true,
+            false => self.back = addr - self.step,
+        }
+
+        Some(unsafe { Address::unchecked(addr) })
+    }
+}
+
+/// Like `len()` on any iterator, this panics if the number of remaining
+/// addresses does not fit in a `usize`.
+impl<T, U> ExactSizeIterator for Steps<T, U>
+where
+    T: Copy + Eq + Zero,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Div<T, Output = T>,
+    usize: TryFrom<T>,
+{
+}
+
+impl<T, U> core::iter::FusedIterator for Steps<T, U>
+where
+    T: Copy + Eq + Zero,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Div<T, Output = T>,
+    usize: TryFrom<T>,
+{
+}
+
+impl<T, U> Address<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + Ord + Zero + One + Checked,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+{
+    /// Iterates over every `U` from this address up to, but excluding, `end`
+    #[inline]
+    pub fn iter_to(self, end: Self) -> Steps<T, U> {
+        Steps::new(self..end, Offset::from_items(T::ONE))
+    }
+
+    /// Iterates in strides of `step` from this address up to, but excluding, `end`
+    #[inline]
+    pub fn step_to(self, end: Self, step: Offset<T, U>) -> Steps<T, U> {
+        Steps::new(self..end, step)
+    }
+}
+
+impl<T, U> Region<T, U>
+where
+    Offset<usize, ()>: Into<Offset<T, ()>>,
+    T: Copy + Ord + Zero + One + Checked,
+    T: Add<T, Output = T>,
+    T: Sub<T, Output = T>,
+    T: Mul<T, Output = T>,
+    T: Div<T, Output = T>,
+{
+    /// Iterates over every `U` in the region
+    ///
+    /// Unlike `Address::iter_to()`, this also works for regions ending at the
+    /// top of the address space.
+    #[inline]
+    pub fn iter(&self) -> Steps<T, U> {
+        self.step_by(Offset::from_items(T::ONE))
+    }
+
+    /// Iterates over the region in strides of `step`
+    #[inline]
+    pub fn step_by(&self, step: Offset<T, U>) -> Steps<T, U> {
+        Steps::inclusive(self.start().raw(), self.last().raw(), step)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn steps() {
+        let start = Address::<usize, u32>::new(0x100);
+        let end = Address::<usize, u32>::new(0x110);
+
+        let all: Vec<_> = start.iter_to(end).map(Address::raw).collect();
+        assert_eq!(all, [0x100, 0x104, 0x108, 0x10c]);
+
+        let rev: Vec<_> = start
+            .step_to(end, Offset::from_items(3))
+            .rev()
+            .map(Address::raw)
+            .collect();
+        assert_eq!(rev, [0x10c, 0x100]);
+
+        assert_eq!(end.iter_to(start).count(), 0);
+        assert_eq!(start.iter_to(start).count(), 0);
+    }
+
+    #[test]
+    fn len() {
+        let start = Address::<usize, u32>::new(0x100);
+        let end = Address::<usize, u32>::new(0x110);
+
+        let mut iter = start.step_to(end, Offset::from_items(3));
+        assert_eq!(iter.len(), 2);
+        iter.next_back();
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+        iter.next();
+        assert_eq!(iter.len(), 0);
+
+        assert_eq!(start.iter_to(end).len(), 4);
+        assert_eq!(end.iter_to(start).len(), 0);
+        assert_eq!(start.step_to(end, Offset::from_items(usize::MAX)).len(), 1);
+
+        let start = Address::<usize, u8>::new(0);
+        let most = Region::new(start..Address::new(usize::MAX)).unwrap();
+        assert_eq!(most.iter().len(), usize::MAX);
+
+        let all = Region::inclusive(start, Address::from(usize::MAX)).unwrap();
+        assert_eq!(all.iter().size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn huge_step() {
+        let start = Address::<usize, Page>::new(0x1000);
+        let end = Address::<usize, Page>::new(0x4000);
+
+        let all: Vec<_> = start
+            .step_to(end, Offset::from_items(usize::MAX))
+            .map(Address::raw)
+            .collect();
+        assert_eq!(all, [0x1000]);
+    }
+
+    #[test]
+    fn top() {
+        let start = Address::from(u64::MAX - 3 * Page::SIZE as u64 + 1).lower::<Page>();
+        let region = Region::from_offset(start, Offset::from_items(3)).unwrap();
+
+        let pages: Vec<_> = region.iter().map(Address::raw).collect();
+        assert_eq!(
+            pages,
+            [
+                0xffff_ffff_ffff_d000,
+                0xffff_ffff_ffff_e000,
+                0xffff_ffff_ffff_f000
+            ]
+        );
+
+        let mut iter = region.iter();
+        assert_eq!(iter.next_back().unwrap().raw(), 0xffff_ffff_ffff_f000);
+        assert_eq!(iter.next().unwrap().raw(), 0xffff_ffff_ffff_d000);
+        assert_eq!(iter.next().unwrap().raw(), 0xffff_ffff_ffff_e000);
+        assert!(iter.next().is_none());
+        assert!(iter.next_back().is_none());
+    }
+
+    #[test]
+    fn hash() {
+        use std::collections::HashSet;
+
+        let set: HashSet<_> = [
+            Address::<usize, u8>::new(1),
+            Address::new(2),
+            Address::new(1),
+        ]
+        .iter()
+        .copied()
+        .collect();
+        assert_eq!(set.len(), 2);
+
+        let set: HashSet<_> = [
+            Offset::<usize, u8>::from_items(1),
+            Offset::from_items(2),
+            Offset::from_items(1),
+        ]
+        .iter()
+        .copied()
+        .collect();
+        assert_eq!(set.len(), 2);
+
+        let set: HashSet<_> = [Register::<u64>::from(1u64), Register::from(1u64)]
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(set.len(), 1);
+    }
+}
@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// A pointer tagging scheme
+///
+/// Each scheme reserves a range of upper address bits which the hardware
+/// ignores during translation. Removing a tag replaces these bits with
+/// copies of a fill bit, which restores the address the hardware would
+/// actually translate.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TagMode {
+    /// aarch64 Top-Byte-Ignore: bits 63:56, filled from bit 55
+    Tbi,
+
+    /// Intel LAM48: bits 62:48, filled from bit 63
+    Lam48,
+
+    /// Intel LAM57: bits 62:57, filled from bit 63
+    Lam57,
+
+    /// AMD Upper Address Ignore: bits 63:57, filled from bit 56
+    Uai,
+}
+
+impl TagMode {
+    /// Returns the lowest bit of the tag
+    #[inline]
+    pub const fn shift(self) -> u32 {
+        match self {
+            Self::Tbi => 56,
+            Self::Lam48 => 48,
+            Self::Lam57 | Self::Uai => 57,
+        }
+    }
+
+    /// Returns the number of bits in the tag
+    #[inline]
+    pub const fn bits(self) -> u32 {
+        match self {
+            Self::Tbi => 8,
+            Self::Lam48 => 15,
+            Self::Lam57 => 6,
+            Self::Uai => 7,
+        }
+    }
+
+    /// Returns the mask of the tag bits in an address
+    #[inline]
+    pub const fn mask(self) -> u64 {
+        ((1 << self.bits()) - 1) << self.shift()
+    }
+
+    /// Returns the bit that is copied into the tag bits on removal
+    #[inline]
+    const fn fill(self) -> u32 {
+        match self {
+            Self::Tbi => 55,
+            Self::Lam48 | Self::Lam57 => 63,
+            Self::Uai => 56,
+        }
+    }
+
+    /// Extracts the tag from the value
+    #[inline]
+    const fn tag(self, value: u64) -> u64 {
+        (value & self.mask()) >> self.shift()
+    }
+
+    /// Removes the tag from the value
+    #[inline]
+    const fn untag(self, value: u64) -> u64 {
+        match value >> self.fill() & 1 {
+            0 => value & !self.mask(),
+            _ => value | self.mask(),
+        }
+    }
+
+    /// Replaces the tag in the value
+    #[inline]
+    const fn with_tag(self, value: u64, tag: u64) -> Result<u64, Error> {
+        match tag >> self.bits() {
+            0 => Ok(value & !self.mask() | tag << self.shift()),
+            _ => Err(Error::OutOfRange),
+        }
+    }
+}
+
+impl<U> Address<u64, U> {
+    /// Returns the tag of the address in the given tagging scheme
+    #[inline]
+    pub fn tag(self, mode: TagMode) -> u64 {
+        mode.tag(self.raw())
+    }
+
+    /// Removes the tag from the address
+    ///
+    /// The tag bits are replaced by copies of the fill bit of the scheme.
+    /// This never changes the alignment of the address.
+    #[inline]
+    pub fn untag(self, mode: TagMode) -> Self {
+        unsafe { Self::unchecked(mode.untag(self.raw())) }
+    }
+
+    /// Replaces the tag of the address
+    ///
+    /// Fails with `Error::OutOfRange` if the tag does not fit in the tag bits
+    /// of the scheme.
+    #[inline]
+    pub fn with_tag(self, mode: TagMode, tag: u64) -> Result<Self, Error> {
+        let value = mode.with_tag(self.raw(), tag)?;
+        Ok(unsafe { Self::unchecked(value) })
+    }
+}
+
+impl Register<u64> {
+    /// Returns the tag of the register value in the given tagging scheme
+    #[inline]
+    pub fn tag(self, mode: TagMode) -> u64 {
+        mode.tag(self.into())
+    }
+
+    /// Removes the tag from the register value
+    ///
+    /// The tag bits are replaced by copies of the fill bit of the scheme.
+    #[inline]
+    pub fn untag(self, mode: TagMode) -> Self {
+        mode.untag(self.into()).into()
+    }
+
+    /// Replaces the tag of the register value
+    ///
+    /// Fails with `Error::OutOfRange` if the tag does not fit in the tag bits
+    /// of the scheme.
+    #[inline]
+    pub fn with_tag(self, mode: TagMode, tag: u64) -> Result<Self, Error> {
+        Ok(mode.with_tag(self.into(), tag)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn masks() {
+        assert_eq!(TagMode::Tbi.mask(), 0xff00_0000_0000_0000);
+        assert_eq!(TagMode::Lam48.mask(), 0x7fff_0000_0000_0000);
+        assert_eq!(TagMode::Lam57.mask(), 0x7e00_0000_0000_0000);
+        assert_eq!(TagMode::Uai.mask(), 0xfe00_0000_0000_0000);
+    }
+
+    #[test]
+    fn address() {
+        let addr = Address::from(0x5a00_7fff_dead_b000u64).lower::<u64>();
+        assert_eq!(addr.tag(TagMode::Tbi), 0x5a);
+        assert_eq!(addr.untag(TagMode::Tbi).raw(), 0x0000_7fff_dead_b000);
+        assert_eq!(addr.untag(TagMode::Lam48).raw(), 0x0000_7fff_dead_b000);
+
+        let kernel = Address::from(0xffff_8000_0000_0000u64).lower::<u64>();
+        let tagged = kernel.with_tag(TagMode::Lam57, 0x15).unwrap();
+        assert_eq!(tagged.raw(), 0xabff_8000_0000_0000);
+        assert_eq!(tagged.tag(TagMode::Lam57), 0x15);
+        assert_eq!(tagged.untag(TagMode::Lam57), kernel);
+        assert_eq!(
+            kernel.with_tag(TagMode::Lam57, 0x40),
+            Err(Error::OutOfRange)
+        );
+
+        let high = Address::from(0x5b80_0000_0000_1000u64).lower::<u64>();
+        assert_eq!(high.untag(TagMode::Tbi).raw(), 0xff80_0000_0000_1000);
+        assert_eq!(high.untag(TagMode::Uai).raw(), 0xff80_0000_0000_1000);
+    }
+
+    #[test]
+    fn register() {
+        let reg = Register::from(0x0000_7fff_0000_1000u64);
+        let tagged = reg.with_tag(TagMode::Uai, 0x7f).unwrap();
+
+        assert_eq!(u64::from(tagged), 0xfe00_7fff_0000_1000);
+        assert_eq!(tagged.tag(TagMode::Uai), 0x7f);
+        assert_eq!(tagged.untag(TagMode::Uai), reg);
+        assert_eq!(reg.with_tag(TagMode::Uai, 0x80), Err(Error::OutOfRange));
+    }
+}
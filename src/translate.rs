@@ -0,0 +1,468 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// The access permissions of a mapping
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Permissions {
+    /// Whether the mapping may be written
+    pub writable: bool,
+
+    /// Whether the mapping may be executed
+    pub executable: bool,
+
+    /// Whether the mapping is accessible from user mode
+    pub user: bool,
+}
+
+impl Permissions {
+    /// Permits all accesses
+    pub const ALL: Self = Self {
+        writable: true,
+        executable: true,
+        user: true,
+    };
+
+    /// Returns the accesses permitted by both `self` and `other`
+    #[inline]
+    pub const fn intersect(self, other: Self) -> Self {
+        Self {
+            writable: self.writable && other.writable,
+            executable: self.executable && other.executable,
+            user: self.user && other.user,
+        }
+    }
+}
+
+/// The result of a successful translation
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Translation {
+    address: PhysAddr<u64, u8>,
+    mapping: Region<u64, u8>,
+    permissions: Permissions,
+}
+
+impl Translation {
+    /// Returns the translated physical address
+    #[inline]
+    pub fn address(&self) -> PhysAddr<u64, u8> {
+        self.address
+    }
+
+    /// Returns the virtual region of the mapping containing the address
+    ///
+    /// For page tables, this is the page which contains the address.
+    #[inline]
+    pub fn mapping(&self) -> Region<u64, u8> {
+        self.mapping
+    }
+
+    /// Returns the size of the mapping containing the address
+    ///
+    /// For page tables, this is the page size. Returns `None` if the
+    /// mapping covers the whole address space.
+    #[inline]
+    pub fn size(&self) -> Option<Offset<u64, u8>> {
+        self.mapping.count()
+    }
+
+    /// Returns the access permissions of the mapping
+    #[inline]
+    pub fn permissions(&self) -> Permissions {
+        self.permissions
+    }
+}
+
+/// Translates virtual addresses to physical addresses
+pub trait Translate {
+    /// Translates a virtual byte address
+    fn lookup(&self, addr: VirtAddr<u64, u8>) -> Result<Translation, Error>;
+
+    /// Translates a virtual address, keeping its type
+    ///
+    /// Fails with `Error::Misaligned` if the translated address is not
+    /// properly aligned for `U`.
+    #[inline]
+    fn translate<U>(&self, addr: VirtAddr<u64, U>) -> Result<PhysAddr<u64, U>, Error>
+    where
+        Self: Sized,
+    {
+        self.lookup(addr.lower())?.address().try_cast()
+    }
+}
+
+/// Translates the first mapping in the table which contains the address
+///
+/// This allows a slice of mappings to be used as a table of memory slots.
+impl<T: Translate> Translate for [T] {
+    fn lookup(&self, addr: VirtAddr<u64, u8>) -> Result<Translation, Error> {
+        for slot in self {
+            match slot.lookup(addr) {
+                Err(Error::Unmapped) => continue,
+                result => return result,
+            }
+        }
+
+        Err(Error::Unmapped)
+    }
+}
+
+/// A table of memory slots
+///
+/// This wraps a slice of mappings so that it is `Sized` and can use
+/// `Translate::translate()`. Lookups behave like those of the slice itself.
+#[derive(Debug)]
+pub struct SlotTable<'a, T>(&'a [T]);
+
+impl<T> Clone for SlotTable<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SlotTable<'_, T> {}
+
+impl<'a, T: Translate> SlotTable<'a, T> {
+    /// Creates a new slot table
+    #[inline]
+    pub const fn new(slots: &'a [T]) -> Self {
+        Self(slots)
+    }
+
+    /// Returns the slots of the table
+    #[inline]
+    pub const fn slots(&self) -> &'a [T] {
+        self.0
+    }
+}
+
+impl<T: Translate> Translate for SlotTable<'_, T> {
+    #[inline]
+    fn lookup(&self, addr: VirtAddr<u64, u8>) -> Result<Translation, Error> {
+        self.0.lookup(addr)
+    }
+}
+
+/// A linear mapping of a virtual region to a physical target address
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OffsetMap {
+    source: Region<u64, u8>,
+    target: PhysAddr<u64, u8>,
+    permissions: Permissions,
+}
+
+impl OffsetMap {
+    /// Creates a new mapping of `source` to `target`
+    ///
+    /// Fails with `Error::Overflow` if the end of the target region
+    /// overflows.
+    #[inline]
+    pub fn new(
+        source: Region<u64, u8>,
+        target: PhysAddr<u64, u8>,
+        permissions: Permissions,
+    ) -> Result<Self, Error> {
+        let span = source.last().raw() - source.start().raw();
+        target.raw().checked_add(span).ok_or(Error::Overflow)?;
+
+        Ok(Self {
+            source,
+            target,
+            permissions,
+        })
+    }
+
+    /// Returns the virtual source region
+    #[inline]
+    pub fn source(&self) -> Region<u64, u8> {
+        self.source
+    }
+
+    /// Returns the physical address of the start of the source region
+    #[inline]
+    pub fn target(&self) -> PhysAddr<u64, u8> {
+        self.target
+    }
+}
+
+impl Translate for OffsetMap {
+    fn lookup(&self, addr: VirtAddr<u64, u8>) -> Result<Translation, Error> {
+        let addr = addr.address();
+        if !self.source.contains(addr) {
+            return Err(Error::Unmapped);
+        }
+
+        Ok(Translation {
+            address: self.target + (addr - self.source.start()),
+            mapping: self.source,
+            permissions: self.permissions,
+        })
+    }
+}
+
+/// A software walker for x86-64 page tables
+///
+/// The page tables are read from `memory`, whose first page is located at
+/// the physical address `base`. The walk starts at the top-level table at
+/// `root`, which is the value of CR3 without its flags. Looking up an
+/// address which is not canonical in the paging mode fails with
+/// `Error::NonCanonical`.
+///
+/// In a confidential VM, entries also carry a memory encryption bit. Set it
+/// with `with_encryption()` so that it is removed from table and frame
+/// addresses.
+pub struct PageTableWalker<'a, T> {
+    memory: &'a Pages<T>,
+    base: PhysAddr<u64, Page>,
+    root: PhysAddr<u64, Page>,
+    mode: PagingMode,
+    encryption: Option<EncryptionMask>,
+}
+
+impl<'a, T: AsRef<[Page]>> PageTableWalker<'a, T> {
+    const PRESENT: u64 = 1 << 0;
+    const WRITABLE: u64 = 1 << 1;
+    const USER: u64 = 1 << 2;
+    const HUGE: u64 = 1 << 7;
+    const NX: u64 = 1 << 63;
+    const ADDRESS: u64 = 0x000f_ffff_ffff_f000;
+
+    /// Creates a new page table walker
+    #[inline]
+    pub fn new(
+        memory: &'a Pages<T>,
+        base: PhysAddr<u64, Page>,
+        root: PhysAddr<u64, Page>,
+        mode: PagingMode,
+    ) -> Self {
+        Self {
+            memory,
+            base,
+            root,
+            mode,
+            encryption: None,
+        }
+    }
+
+    /// Sets the memory encryption bit used by the page tables
+    #[inline]
+    pub fn with_encryption(self, mask: EncryptionMask) -> Self {
+        Self {
+            encryption: Some(mask),
+            ..self
+        }
+    }
+
+    /// Returns the physical address in an entry or CR3 value
+    fn address(&self, entry: u64) -> u64 {
+        let value = entry & Self::ADDRESS;
+        match self.encryption {
+            Some(mask) => mask.clear_entry(value),
+            None => value,
+        }
+    }
+
+    /// Reads the entry at `index` in the table at physical address `table`
+    fn entry(&self, table: u64, index: usize) -> Result<u64, Error> {
+        let page = table
+            .checked_sub(self.base.raw())
+            .ok_or(Error::InvalidPageTable)?;
+        let pages: &[Page] = self.memory.as_ref();
+        let page = usize::try_from(page / Page::SIZE as u64)
+            .ok()
+            .and_then(|page| pages.get(page))
+            .ok_or(Error::InvalidPageTable)?;
+
+        let mut entry = [0; 8];
+        entry.copy_from_slice(&page[index * 8..][..8]);
+        Ok(u64::from_le_bytes(entry))
+    }
+}
+
+impl<T: AsRef<[Page]>> Translate for PageTableWalker<'_, T> {
+    fn lookup(&self, addr: VirtAddr<u64, u8>) -> Result<Translation, Error> {
+        let addr = addr.address();
+        if !addr.is_canonical(self.mode) {
+            return Err(Error::NonCanonical);
+        }
+
+        let layout = PageTableLayout::from(self.mode);
+        let indices = addr.page_table_indices(layout);
+        let mut table = self.address(self.root.raw());
+        let mut permissions = Permissions::ALL;
+
+        for level in (0..layout.levels()).rev() {
+            let index = indices.index(level).ok_or(Error::InvalidPageTable)?;
+            let entry = self.entry(table, index)?;
+            if entry & Self::PRESENT == 0 {
+                return Err(Error::Unmapped);
+            }
+
+            permissions = permissions.intersect(Permissions {
+                writable: entry & Self::WRITABLE != 0,
+                executable: entry & Self::NX == 0,
+                user: entry & Self::USER != 0,
+            });
+
+            if level == 0 || entry & Self::HUGE != 0 {
+                // Huge pages only exist for 2 MiB and 1 GiB pages.
+                if level > 2 {
+                    return Err(Error::InvalidPageTable);
+                }
+
+                let size = 1u64 << layout.shift(level);
+                let page = addr.raw() & !(size - 1);
+                let frame = self.address(entry) & !(size - 1);
+
+                let mapping =
+                    Region::from_offset(Address::from(page).lower(), Offset::from_items(size))
+                        .ok_or(Error::InvalidPageTable)?;
+
+                let address = Address::from(frame | (addr.raw() & (size - 1))).lower();
+
+                return Ok(Translation {
+                    address: PhysAddr::new(address),
+                    mapping,
+                    permissions,
+                });
+            }
+
+            table = self.address(entry);
+        }
+
+        Err(Error::InvalidPageTable)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn virt<U>(value: u64) -> VirtAddr<u64, U> {
+        VirtAddr::new(Address::from(value).lower())
+    }
+
+    fn phys<U>(value: u64) -> PhysAddr<u64, U> {
+        PhysAddr::new(Address::from(value).lower())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn set(pages: &mut Pages<alloc::vec::Vec<Page>>, table: usize, index: usize, entry: u64) {
+        pages[table][index * 8..][..8].copy_from_slice(&entry.to_le_bytes());
+    }
+
+    #[test]
+    fn offset() {
+        let map = OffsetMap::new(
+            Region::bytes(0x1000, 0x3000),
+            phys(0x8000_0000),
+            Permissions::ALL,
+        )
+        .unwrap();
+
+        let word = virt::<u64>(0x1800);
+        assert_eq!(map.translate(word).unwrap().raw(), 0x8000_0800);
+        assert_eq!(map.translate(virt::<u8>(0x3000)), Err(Error::Unmapped));
+
+        let map = OffsetMap::new(Region::bytes(0, 0x10), phys(1), Permissions::ALL).unwrap();
+        assert_eq!(
+            map.translate(virt::<u64>(0)),
+            Err(Error::Misaligned {
+                address: 1,
+                align: 8
+            })
+        );
+        assert_eq!(
+            OffsetMap::new(Region::bytes(0, 0x10), phys(u64::MAX), Permissions::ALL),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn slots() {
+        let readonly = Permissions {
+            writable: false,
+            ..Permissions::ALL
+        };
+
+        let slots = [
+            OffsetMap::new(Region::bytes(0, 0x1000), phys(0x10_0000), Permissions::ALL).unwrap(),
+            OffsetMap::new(Region::bytes(0x4000, 0x5000), phys(0x20_0000), readonly).unwrap(),
+        ];
+
+        let t = slots[..].lookup(virt(0x4010)).unwrap();
+        assert_eq!(t.address().raw(), 0x20_0010);
+        assert_eq!(t.permissions(), readonly);
+        assert_eq!(t.size(), Some(Offset::from_items(0x1000)));
+        assert_eq!(slots[..].lookup(virt(0x2000)), Err(Error::Unmapped));
+
+        let table = SlotTable::new(&slots);
+        let word = virt::<u64>(0x0ff8);
+        assert_eq!(table.translate(word).unwrap().raw(), 0x10_0ff8);
+        assert_eq!(table.translate(virt::<u8>(0x5000)), Err(Error::Unmapped));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn walk() {
+        const BASE: u64 = 0x10_0000;
+        let mut pages = Pages::copy_into(&[], 4 * Page::SIZE, 0);
+
+        // PML4[0] -> PDPT, PDPT[0] -> PD, PDPT[1] is a 1 GiB page.
+        set(&mut pages, 0, 0, (BASE + 0x1000) | 0x7);
+        set(&mut pages, 1, 0, (BASE + 0x2000) | 0x7);
+        set(&mut pages, 1, 1, 0x8000_0000 | 0x83 | 1 << 63);
+
+        // PD[0] -> PT, PD[1] is a read-only 2 MiB page.
+        set(&mut pages, 2, 0, (BASE + 0x3000) | 0x7);
+        set(&mut pages, 2, 1, 0x4000_0000 | 0x85);
+
+        // PT[5] is a 4 KiB page.
+        set(&mut pages, 3, 5, 0xabc_d000 | 0x7);
+
+        let walker = PageTableWalker::new(&pages, phys(BASE), phys(BASE), PagingMode::Level4);
+
+        let t = walker.lookup(virt(0x5123)).unwrap();
+        assert_eq!(t.address().raw(), 0xabc_d123);
+        assert_eq!(t.size(), Some(Offset::from_items(0x1000)));
+        assert_eq!(t.permissions(), Permissions::ALL);
+
+        let t = walker.lookup(virt(0x2f_0000)).unwrap();
+        assert_eq!(t.address().raw(), 0x400f_0000);
+        assert_eq!(t.size(), Some(Offset::from_items(0x20_0000)));
+        assert!(!t.permissions().writable);
+
+        let t = walker.lookup(virt(0x4000_1000)).unwrap();
+        assert_eq!(t.address().raw(), 0x8000_1000);
+        assert_eq!(t.size(), Some(Offset::from_items(0x4000_0000)));
+        assert!(!t.permissions().executable && !t.permissions().user);
+
+        assert_eq!(walker.lookup(virt(0x6000)), Err(Error::Unmapped));
+        assert_eq!(
+            walker.lookup(virt(0x8000_0000_0000)),
+            Err(Error::NonCanonical)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn walk_encrypted() {
+        const BASE: u64 = 0x10_0000;
+        let mask = EncryptionMask::sev(51).unwrap();
+        let mut pages = Pages::copy_into(&[], 3 * Page::SIZE, 0);
+
+        // PML4[0] -> PDPT, PDPT[0] -> PD, PD[0] is a private 2 MiB page.
+        set(&mut pages, 0, 0, mask.private_entry((BASE + 0x1000) | 0x7));
+        set(&mut pages, 1, 0, mask.private_entry((BASE + 0x2000) | 0x7));
+        set(&mut pages, 2, 0, mask.private_entry(0x4000_0000 | 0x87));
+
+        let root = mask.private(Address::from(BASE).lower()).unwrap();
+        let walker =
+            PageTableWalker::new(&pages, phys(BASE), PhysAddr::new(root), PagingMode::Level4);
+        assert_eq!(walker.lookup(virt(0x1234)), Err(Error::InvalidPageTable));
+
+        let walker = walker.with_encryption(mask);
+        let t = walker.lookup(virt(0x1234)).unwrap();
+        assert_eq!(t.address().raw(), 0x4000_1234);
+    }
+}
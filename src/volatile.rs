@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::marker::PhantomData;
+use core::mem::size_of;
+
+/// An access mode for a `VolatileAddress`
+pub trait Access {}
+
+/// An access mode which permits reads
+pub trait Readable: Access {}
+
+/// An access mode which permits writes
+pub trait Writable: Access {}
+
+/// Read and write access
+pub enum ReadWrite {}
+
+/// Read-only access
+pub enum ReadOnly {}
+
+/// Write-only access
+pub enum WriteOnly {}
+
+impl Access for ReadWrite {}
+impl Readable for ReadWrite {}
+impl Writable for ReadWrite {}
+
+impl Access for ReadOnly {}
+impl Readable for ReadOnly {}
+
+impl Access for WriteOnly {}
+impl Writable for WriteOnly {}
+
+/// An address which is accessed with volatile reads and writes
+///
+/// This type is intended for memory-mapped I/O and shared firmware pages.
+/// The validity of the memory is promised once, when the `VolatileAddress`
+/// is created, which makes all later accesses safe. The access mode `A`
+/// restricts the value to reads (`ReadOnly`), writes (`WriteOnly`) or both
+/// (`ReadWrite`).
+#[repr(transparent)]
+pub struct VolatileAddress<U, A: Access = ReadWrite>(PtrAddress<U>, PhantomData<A>);
+
+impl<U, A: Access> Clone for VolatileAddress<U, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<U, A: Access> Copy for VolatileAddress<U, A> {}
+
+impl<U, A: Access> core::fmt::Debug for VolatileAddress<U, A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "VolatileAddress(0x{:01$x})",
+            self.0.address().raw(),
+            size_of::<usize>() * 2
+        ))
+    }
+}
+
+impl<U, A: Access> VolatileAddress<U, A> {
+    /// Creates a new `VolatileAddress` from an `Address`
+    ///
+    /// The pointer is created with exposed provenance.
+    ///
+    /// # Safety
+    ///
+    /// The caller MUST ensure that the address is valid for volatile accesses
+    /// of `U`, as permitted by `A`, for as long as the value or any copy of it
+    /// is in use.
+    #[inline]
+    pub unsafe fn new<T>(addr: Address<T, U>) -> Self
+    where
+        Address<T, U>: Into<Address<usize, U>>,
+    {
+        Self::from_ptr(PtrAddress::unchecked(addr.as_mut_ptr()))
+    }
+
+    /// Creates a new `VolatileAddress` from a `PtrAddress`
+    ///
+    /// # Safety
+    ///
+    /// The caller MUST ensure that the pointer is valid for volatile accesses
+    /// of `U`, as permitted by `A`, for as long as the value or any copy of it
+    /// is in use.
+    #[inline]
+    pub const unsafe fn from_ptr(ptr: PtrAddress<U>) -> Self {
+        Self(ptr, PhantomData)
+    }
+
+    /// Returns the address
+    #[inline]
+    pub fn address(self) -> Address<usize, U> {
+        self.0.address()
+    }
+
+    /// Returns the underlying pointer
+    #[inline]
+    pub const fn as_ptr(self) -> PtrAddress<U> {
+        self.0
+    }
+
+    /// Restricts the value to reads
+    #[inline]
+    pub fn read_only(self) -> VolatileAddress<U, ReadOnly>
+    where
+        A: Readable,
+    {
+        VolatileAddress(self.0, PhantomData)
+    }
+
+    /// Restricts the value to writes
+    #[inline]
+    pub fn write_only(self) -> VolatileAddress<U, WriteOnly>
+    where
+        A: Writable,
+    {
+        VolatileAddress(self.0, PhantomData)
+    }
+}
+
+impl<U: Copy, A: Readable> VolatileAddress<U, A> {
+    /// Performs a volatile read of the value
+    #[inline]
+    pub fn read(self) -> U {
+        unsafe { self.0.as_ptr().read_volatile() }
+    }
+}
+
+impl<U: Copy, A: Writable> VolatileAddress<U, A> {
+    /// Performs a volatile write of the value
+    #[inline]
+    pub fn write(self, value: U) {
+        unsafe { self.0.as_mut_ptr().write_volatile(value) }
+    }
+}
+
+impl<U: Copy, A: Readable + Writable> VolatileAddress<U, A> {
+    /// Reads the value, applies `f` to it and writes the result back
+    ///
+    /// The read and the write are separate volatile accesses; this is not
+    /// an atomic operation.
+    #[inline]
+    pub fn update(self, f: impl FnOnce(U) -> U) {
+        self.write(f(self.read()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn access() {
+        let mut value = 0x10u32;
+        let rw = unsafe { VolatileAddress::<u32>::from_ptr(PtrAddress::from(&mut value)) };
+
+        assert_eq!(rw.read(), 0x10);
+        rw.write(0x20);
+        rw.update(|v| v | 1);
+        assert_eq!(rw.read_only().read(), 0x21);
+
+        rw.write_only().write(0x30);
+        assert_eq!(value, 0x30);
+    }
+
+    #[test]
+    fn address() {
+        let mut value = 0u64;
+        let addr = Address::<usize, u64>::from(&mut value as *mut u64);
+        let rw = unsafe { VolatileAddress::<u64>::new(addr) };
+        rw.write(u64::MAX);
+
+        assert_eq!(rw.address(), addr);
+        assert_eq!(core::mem::replace(&mut value, 0), u64::MAX);
+    }
+}